@@ -0,0 +1,70 @@
+use crate::context::Context;
+use crate::*;
+use std::fmt;
+use std::io::Cursor;
+use std::ops::Deref;
+
+/// An ICC profile that reads its tags on demand from borrowed data, e.g. a memory-mapped file.
+///
+/// Unlike `Profile::new_icc()`, opening it doesn't copy the data, and tags that are never used are never parsed.
+/// It derefs to a read-only `Profile`, so all getters work as usual.
+pub struct BorrowedProfile<'a, Ctx = GlobalContext> {
+    profile: Profile<Ctx>,
+    _data: PhantomData<&'a [u8]>,
+}
+
+impl<'a> BorrowedProfile<'a> {
+    /// Parse ICC profile header from the borrowed data. Tags are read later, when needed.
+    #[inline]
+    pub fn new(data: &'a [u8]) -> LCMSResult<Self> {
+        Self::new_context(GlobalContext::new(), data)
+    }
+}
+
+impl<'a, Ctx: Context> BorrowedProfile<'a, Ctx> {
+    /// See `new()`
+    pub fn new_context(context: impl AsRef<Ctx>, data: &'a [u8]) -> LCMSResult<Self> {
+        if data.is_empty() {
+            return Err(Error::MissingData);
+        }
+        // The profile keeps the cursor, and can't outlive 'a
+        let (profile, _) = unsafe { Profile::new_reader_context(context, Cursor::new(data)) }
+            .map_err(|_| Error::ObjectCreationError)?;
+        Ok(Self {
+            profile,
+            _data: PhantomData,
+        })
+    }
+}
+
+impl<'a, Ctx> Deref for BorrowedProfile<'a, Ctx> {
+    type Target = Profile<Ctx>;
+
+    #[inline]
+    fn deref(&self) -> &Profile<Ctx> {
+        &self.profile
+    }
+}
+
+impl<'a> fmt::Debug for BorrowedProfile<'a> {
+    #[cold]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.profile, f)
+    }
+}
+
+#[test]
+fn borrowed() {
+    let icc = Profile::new_srgb().icc().unwrap();
+    let prof = BorrowedProfile::new(&icc).unwrap();
+    assert_eq!(ColorSpaceSignature::RgbData, prof.color_space());
+    assert!(prof.info(InfoType::Description, Locale::none()).unwrap().contains("sRGB"));
+    assert!(matches!(prof.read_tag(TagSignature::RedTRCTag), Tag::ToneCurve(_)));
+
+    let t = Transform::new(&prof, PixelFormat::RGB_8, &Profile::new_srgb(), PixelFormat::RGB_8, Intent::Perceptual).unwrap();
+    let mut px = [[1u8, 2, 3]];
+    t.transform_in_place(&mut px);
+
+    assert!(BorrowedProfile::new(&[]).is_err());
+    assert!(BorrowedProfile::new(&icc[..100]).is_err());
+}
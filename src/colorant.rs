@@ -0,0 +1,180 @@
+use crate::*;
+use foreign_types::ForeignTypeRef;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::ptr;
+
+/// Name and PCS value of a colorant (ink) of an n-colour device
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Colorant {
+    /// Up to 32 bytes
+    pub name: String,
+    /// Color encoded as 16-bit Lab or XYZ, depending on the PCS of the profile
+    pub pcs: [u16; 3],
+}
+
+foreign_type! {
+    /// Names and PCS values of device colorants, stored in `ColorantTableTag` and `ColorantTableOutTag`.
+    ///
+    /// Owned version of `ColorantTableRef`
+    pub unsafe type ColorantTable {
+        type CType = ffi::NAMEDCOLORLIST;
+        fn drop = ffi::cmsFreeNamedColorList;
+        fn clone = ffi::cmsDupNamedColorList;
+    }
+}
+
+impl ColorantTable {
+    /// Colorants in the order of device channels. There can be at most 16.
+    pub fn new(colorants: &[Colorant]) -> LCMSResult<Self> {
+        if colorants.len() > ffi::MAXCHANNELS {
+            return Err(Error::InvalidChannels);
+        }
+        // colorants have no prefix or suffix
+        let empty = CStr::from_bytes_with_nul(&[0]).unwrap();
+        let mut table: Self = unsafe {
+            Error::if_null(ffi::cmsAllocNamedColorList(ptr::null_mut(), colorants.len() as u32, 0, empty.as_ptr(), empty.as_ptr()))?
+        };
+        for c in colorants {
+            table.append(c)?;
+        }
+        Ok(table)
+    }
+}
+
+impl ColorantTableRef {
+    /// Number of colorants
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.as_named_color_list().len()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Colorant of the device channel at this index
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<Colorant> {
+        self.as_named_color_list().get(index).map(|c| Colorant { name: c.name, pcs: c.pcs })
+    }
+
+    /// All colorants, in the order of device channels
+    #[must_use]
+    pub fn colorants(&self) -> Vec<Colorant> {
+        (0..self.len()).filter_map(|i| self.get(i)).collect()
+    }
+
+    /// Adds a colorant for the next device channel. There can be at most 16.
+    pub fn append(&mut self, colorant: &Colorant) -> LCMSResult<()> {
+        if self.len() >= ffi::MAXCHANNELS {
+            return Err(Error::InvalidChannels);
+        }
+        // LCMS writes the names in 32-byte fields
+        if colorant.name.len() > 32 {
+            return Err(Error::InvalidString);
+        }
+        let name = CString::new(colorant.name.as_str()).map_err(|_| Error::InvalidString)?;
+        let mut pcs = colorant.pcs;
+        let mut unused = [0; ffi::MAXCHANNELS];
+        if 0 != unsafe { ffi::cmsAppendNamedColor(self.as_ptr(), name.as_ptr(), pcs.as_mut_ptr(), unused.as_mut_ptr()) } {
+            Ok(())
+        } else {
+            Err(Error::ObjectCreationError)
+        }
+    }
+
+    /// LCMS uses the same structure for colorant tables and named color palettes
+    #[inline]
+    #[must_use]
+    pub fn as_named_color_list(&self) -> &NamedColorListRef {
+        unsafe { NamedColorListRef::from_ptr(self.as_ptr()) }
+    }
+}
+
+impl fmt::Debug for ColorantTableRef {
+    #[cold]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.colorants()).finish()
+    }
+}
+
+/// Order in which colorants are printed, as stored in `ColorantOrderTag`.
+///
+/// Each entry is an index of a device channel (and of the colorant in `ColorantTable`), printed first to last.
+#[repr(transparent)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ColorantOrder([u8; ffi::MAXCHANNELS]);
+
+impl ColorantOrder {
+    /// Channel indices, printed first to last. There can be at most 16, and `0xFF` is reserved.
+    pub fn new(order: &[u8]) -> LCMSResult<Self> {
+        if order.len() > ffi::MAXCHANNELS || order.contains(&0xFF) {
+            return Err(Error::InvalidChannels);
+        }
+        // LCMS marks unused entries with 0xFF
+        let mut out = [0xFF; ffi::MAXCHANNELS];
+        out[..order.len()].copy_from_slice(order);
+        Ok(Self(out))
+    }
+
+    /// Channel indices, printed first to last
+    #[inline]
+    #[must_use]
+    pub fn as_slice(&self) -> &[u8] {
+        let len = self.0.iter().position(|&c| c == 0xFF).unwrap_or(self.0.len());
+        &self.0[..len]
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.as_slice().to_vec()
+    }
+
+    /// LCMS allocates a full array for the tag, so the reference can be cast
+    pub(crate) unsafe fn from_ptr<'a>(ptr: *const u8) -> &'a Self {
+        &*ptr.cast::<Self>()
+    }
+
+    pub(crate) fn as_ptr(&self) -> *const u8 {
+        self.0.as_ptr()
+    }
+}
+
+impl fmt::Debug for ColorantOrder {
+    #[cold]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ColorantOrder({:?})", self.as_slice())
+    }
+}
+
+#[test]
+fn colorant_table() {
+    let cyan = Colorant { name: "Cyan".into(), pcs: [1, 2, 3] };
+    let orange = Colorant { name: "Orange".into(), pcs: [4, 5, 6] };
+    let mut table = ColorantTable::new(&[]).unwrap();
+    assert!(table.is_empty());
+    table.append(&cyan).unwrap();
+    table.append(&orange).unwrap();
+    assert_eq!(vec![cyan.clone(), orange], ColorantTable::new(&table.colorants()).unwrap().to_owned().colorants());
+    assert_eq!(Some(cyan.clone()), table.get(0));
+    assert_eq!(None, table.get(2));
+    assert_eq!(Err(Error::InvalidString), table.append(&Colorant { name: "x".repeat(33), pcs: [0; 3] }));
+    let mut full = ColorantTable::new(&vec![cyan.clone(); 16]).unwrap();
+    assert_eq!(Err(Error::InvalidChannels), full.append(&cyan));
+    assert_eq!(Err(Error::InvalidChannels), ColorantTable::new(&vec![cyan; 17]).map(|_| ()));
+}
+
+#[test]
+fn colorant_order() {
+    let order = ColorantOrder::new(&[3, 0, 2, 1]).unwrap();
+    assert_eq!(&[3, 0, 2, 1], order.as_slice());
+    assert_eq!(0, ColorantOrder::new(&[]).unwrap().as_slice().len());
+    assert_eq!(16, ColorantOrder::new(&[1; 16]).unwrap().to_vec().len());
+    assert_eq!(Err(Error::InvalidChannels), ColorantOrder::new(&[1; 17]));
+    assert_eq!(Err(Error::InvalidChannels), ColorantOrder::new(&[0xFF]));
+}
@@ -0,0 +1,165 @@
+use crate::ffi::wchar_t;
+use crate::*;
+use foreign_types::ForeignTypeRef;
+use std::fmt;
+use std::marker::PhantomData;
+use std::os::raw::c_void;
+use std::ptr;
+
+foreign_type! {
+    /// Dictionary of name/value strings, stored in the `MetaTag` of V4 profiles.
+    ///
+    /// Entries can also have localized display names and values. Most methods are implemented on `DictRef`.
+    pub unsafe type Dict {
+        type CType = c_void;
+        fn drop = ffi::cmsDictFree;
+        fn clone = dict_dup;
+    }
+}
+
+/// LCMS returns NULL when it runs out of memory, which can't be a `Dict`. Use `try_to_owned()` to handle it.
+unsafe fn dict_dup(dict: *mut c_void) -> *mut c_void {
+    let copy = ffi::cmsDictDup(dict);
+    assert!(!copy.is_null(), "Out of memory");
+    copy
+}
+
+impl Dict {
+    /// Allocates an empty dictionary
+    pub fn new() -> LCMSResult<Self> {
+        unsafe { Error::if_null(ffi::cmsDictAlloc(ptr::null_mut())) }
+    }
+}
+
+/// Entry of a `Dict`
+#[derive(Debug, Clone)]
+pub struct DictEntry<'a> {
+    pub name: String,
+    pub value: Option<String>,
+    /// Localized version of the name
+    pub display_name: Option<&'a MLURef>,
+    /// Localized version of the value
+    pub display_value: Option<&'a MLURef>,
+}
+
+impl DictRef {
+    /// Copies the dictionary, failing instead of panicking if LCMS can't copy it
+    pub fn try_to_owned(&self) -> LCMSResult<Dict> {
+        unsafe { Error::if_null(ffi::cmsDictDup(self.as_ptr())) }
+    }
+
+    /// Adds an entry. LCMS doesn't check for duplicate names.
+    pub fn add(&mut self, name: &str, value: Option<&str>, display_name: Option<&MLURef>, display_value: Option<&MLURef>) -> LCMSResult<()> {
+        let name = wide(name)?;
+        let value = value.map(wide).transpose()?;
+        let ok = unsafe {
+            ffi::cmsDictAddEntry(
+                self.as_ptr(),
+                name.as_ptr(),
+                value.as_ref().map_or(ptr::null(), |v| v.as_ptr()),
+                display_name.map_or(ptr::null(), |m| m.as_ptr()),
+                display_value.map_or(ptr::null(), |m| m.as_ptr()),
+            ) != 0
+        };
+        if ok { Ok(()) } else { Err(Error::ObjectCreationError) }
+    }
+
+    /// Value of the first entry with this name
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.iter().find(|e| e.name == name)?.value
+    }
+
+    /// Iterate over all entries. The order is the reverse of adding them.
+    #[inline]
+    #[must_use]
+    pub fn iter(&self) -> DictIter<'_> {
+        DictIter {
+            entry: unsafe { ffi::cmsDictGetEntryList(self.as_ptr()) },
+            _dict: PhantomData,
+        }
+    }
+}
+
+fn wide(text: &str) -> LCMSResult<Vec<wchar_t>> {
+    if text.contains('\0') {
+        return Err(Error::InvalidString);
+    }
+    Ok(text.chars().map(|c| c as wchar_t).chain(Some(0)).collect())
+}
+
+unsafe fn from_wide(mut text: *const wchar_t) -> Option<String> {
+    if text.is_null() {
+        return None;
+    }
+    let mut out = String::new();
+    while *text != 0 {
+        out.push(char::from_u32(*text as u32).unwrap_or(char::REPLACEMENT_CHARACTER));
+        text = text.add(1);
+    }
+    Some(out)
+}
+
+/// Iterator over entries of `DictRef`
+pub struct DictIter<'a> {
+    entry: *const ffi::DICTentry,
+    _dict: PhantomData<&'a DictRef>,
+}
+
+impl<'a> Iterator for DictIter<'a> {
+    type Item = DictEntry<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.entry.is_null() {
+            return None;
+        }
+        unsafe {
+            let e = &*self.entry;
+            self.entry = ffi::cmsDictNextEntry(self.entry);
+            Some(DictEntry {
+                name: from_wide(e.Name).unwrap_or_default(),
+                value: from_wide(e.Value),
+                display_name: e.DisplayName.as_ref().map(|m| MLURef::from_ptr(m as *const _ as *mut _)),
+                display_value: e.DisplayValue.as_ref().map(|m| MLURef::from_ptr(m as *const _ as *mut _)),
+            })
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a DictRef {
+    type Item = DictEntry<'a>;
+    type IntoIter = DictIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl fmt::Debug for DictRef {
+    #[cold]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter().map(|e| (e.name, e.value))).finish()
+    }
+}
+
+#[test]
+fn dict() {
+    let mut d = Dict::new().unwrap();
+    assert_eq!(0, d.iter().count());
+    let mut mlu = MLU::new(1);
+    assert!(mlu.set_text("Kalibriert", Locale::new("de_DE")));
+    d.add("build", Some("1.2.3"), None, None).unwrap();
+    d.add("calibrated", Some("2024-01-31"), Some(&mlu), None).unwrap();
+    d.add("empty", None, None, None).unwrap();
+    assert_eq!(Err(Error::InvalidString), d.add("nul\0", None, None, None));
+
+    let copy = d.try_to_owned().unwrap();
+    assert_eq!(Some("1.2.3".to_owned()), copy.get("build"));
+    assert_eq!(None, copy.get("empty"));
+    assert_eq!(None, copy.get("missing"));
+    let calibrated = copy.iter().find(|e| e.name == "calibrated").unwrap();
+    assert_eq!(Ok("Kalibriert".to_owned()), calibrated.display_name.unwrap().text(Locale::new("de_DE")));
+    assert!(calibrated.display_value.is_none());
+    assert_eq!(3, copy.iter().count());
+    assert_eq!(3, copy.clone().iter().count());
+}
@@ -1,3 +1,4 @@
+use crate::{ColorSpaceSignature, TagSignature};
 use foreign_types::ForeignType;
 use std::error::Error as StdError;
 use std::fmt;
@@ -7,6 +8,19 @@ pub enum Error {
     ObjectCreationError,
     MissingData,
     InvalidString,
+    /// No device channels or more than LCMS supports, or a reserved channel index
+    InvalidChannels,
+    /// Image dimensions or strides are too large to address, or too large for LCMS
+    SizeOverflow,
+    /// The pixel format is for a different color space than the profile's color space (given)
+    ColorSpaceMismatch(ColorSpaceSignature),
+    /// Size of the pixel type doesn't match number of bytes per pixel in the `PixelFormat`
+    /// (or bytes per channel for planar formats)
+    PixelSizeMismatch { expected: usize, actual: usize },
+    /// The `PixelFormat` has a different number of channels than the profile's color space
+    ChannelCountMismatch { expected: usize, actual: usize },
+    /// The tag with this signature needs a different type of data (name of the `Tag` variant)
+    TagTypeMismatch { sig: TagSignature, expected: &'static str },
 }
 
 impl Error {
@@ -26,11 +40,17 @@ pub type LCMSResult<T> = Result<T, Error>;
 impl fmt::Display for Error {
     #[cold]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(match *self {
-            Error::ObjectCreationError => "Could not create the object.\nThe reason is not known, but it's usually caused by wrong input parameters.",
-            Error::InvalidString => "String is not valid. Contains unsupported characters or is too long.",
-            Error::MissingData => "Requested data is empty or does not exist.",
-        })
+        match *self {
+            Error::ObjectCreationError => f.write_str("Could not create the object.\nThe reason is not known, but it's usually caused by wrong input parameters."),
+            Error::InvalidString => f.write_str("String is not valid. Contains unsupported characters or is too long."),
+            Error::InvalidChannels => f.write_str("Number of device channels must be between 1 and 16, and channel indices can't be reserved."),
+            Error::SizeOverflow => f.write_str("Image dimensions or strides are too large."),
+            Error::MissingData => f.write_str("Requested data is empty or does not exist."),
+            Error::ColorSpaceMismatch(cs) => write!(f, "The pixel format doesn't match the profile's color space {cs:?}"),
+            Error::PixelSizeMismatch { expected, actual } => write!(f, "The pixel format needs {expected} bytes per pixel, but the pixel type has {actual}"),
+            Error::ChannelCountMismatch { expected, actual } => write!(f, "The profile's color space has {expected} channels, but the pixel format has {actual}"),
+            Error::TagTypeMismatch { sig, expected } => write!(f, "The tag {sig:?} needs {expected} data"),
+        }
     }
 }
 
@@ -71,6 +71,12 @@ impl<T: CacheFlag> ops::BitOr<Flags<T>> for Flags<AllowCache> {
     }
 }
 
+impl Flags {
+    pub(crate) fn from_bits(bits: u32) -> Self {
+        Flags(bits, AllowCache)
+    }
+}
+
 impl<T: CacheFlag> Flags<T> {
     pub(crate) fn bits(&self) -> u32 {
         self.0
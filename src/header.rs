@@ -0,0 +1,220 @@
+use crate::*;
+use std::fmt;
+use std::ops;
+use std::os::raw::c_int;
+
+/// Calendar date and time, as stored in ICC profiles (without a time zone, conventionally UTC)
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    pub year: u16,
+    /// 1-12
+    pub month: u8,
+    /// 1-31
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02} {:02}:{:02}:{:02}", self.year, self.month, self.day, self.hour, self.minute, self.second)
+    }
+}
+
+/// ICC specification version of a profile, e.g. 4.3.0
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IccVersion {
+    pub major: u8,
+    /// 0-15
+    pub minor: u8,
+    /// 0-15
+    pub bugfix: u8,
+}
+
+impl IccVersion {
+    /// Version 2.1, for legacy software
+    pub const V2_1: IccVersion = IccVersion::new(2, 1, 0);
+    /// Version 4.3
+    pub const V4_3: IccVersion = IccVersion::new(4, 3, 0);
+
+    #[inline]
+    #[must_use]
+    pub const fn new(major: u8, minor: u8, bugfix: u8) -> Self {
+        Self { major, minor, bugfix }
+    }
+
+    /// Decodes the version in the same format as it is stored in the header (major version in BCD, then minor and bugfix nibbles)
+    #[must_use]
+    pub fn from_encoded(encoded: u32) -> Self {
+        let [major, minor_bugfix, _, _] = encoded.to_be_bytes();
+        Self {
+            major: (major >> 4) * 10 + (major & 15),
+            minor: minor_bugfix >> 4,
+            bugfix: minor_bugfix & 15,
+        }
+    }
+
+    /// Encodes the version in the same format as it is stored in the header
+    #[must_use]
+    pub fn encoded(self) -> u32 {
+        let major = ((self.major / 10) << 4) | (self.major % 10);
+        let minor_bugfix = (self.minor.min(15) << 4) | self.bugfix.min(15);
+        u32::from_be_bytes([major, minor_bugfix, 0, 0])
+    }
+}
+
+impl fmt::Display for IccVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.bugfix)
+    }
+}
+
+/// C `struct tm` as used by LCMS. Only the standard fields are used, the rest is room for platform-specific extras.
+#[repr(C)]
+#[derive(Default)]
+pub(crate) struct Tm {
+    sec: c_int,
+    min: c_int,
+    hour: c_int,
+    mday: c_int,
+    mon: c_int,
+    year: c_int,
+    wday: c_int,
+    yday: c_int,
+    isdst: c_int,
+    _platform_specific: [usize; 4],
+}
+
+impl Tm {
+    pub(crate) fn date_time(&self) -> Option<DateTime> {
+        if self.year == 0 && self.mday == 0 {
+            return None;
+        }
+        Some(DateTime {
+            year: (self.year + 1900).try_into().ok()?,
+            month: (self.mon + 1).try_into().ok()?,
+            day: self.mday.try_into().ok()?,
+            hour: self.hour.try_into().ok()?,
+            minute: self.min.try_into().ok()?,
+            second: self.sec.try_into().ok()?,
+        })
+    }
+
+    /// Copies the standard fields from a `struct tm` allocated by LCMS, which can be smaller than `Tm`
+    pub(crate) unsafe fn from_ptr(tm: *const Tm) -> Self {
+        let mut out = Self::default();
+        std::ptr::copy_nonoverlapping(tm.cast::<c_int>(), (&mut out as *mut Self).cast::<c_int>(), 9);
+        out
+    }
+
+    pub(crate) fn new(date: &DateTime) -> Self {
+        Self {
+            sec: date.second.into(),
+            min: date.minute.into(),
+            hour: date.hour.into(),
+            mday: date.day.into(),
+            mon: c_int::from(date.month) - 1,
+            year: c_int::from(date.year) - 1900,
+            ..Self::default()
+        }
+    }
+}
+
+/// Profile flags from the header. Can be OR-ed together with `|`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct HeaderFlags(pub u32);
+
+impl HeaderFlags {
+    /// The profile is embedded in a file. If not set, it's a standalone file.
+    pub const EMBEDDED: HeaderFlags = HeaderFlags(1);
+    /// The profile can't be used independently from the embedded color data
+    pub const USE_WITH_EMBEDDED_DATA_ONLY: HeaderFlags = HeaderFlags(2);
+
+    #[inline]
+    #[must_use]
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl ops::BitOr for HeaderFlags {
+    type Output = Self;
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+/// Device attributes from the header. Can be OR-ed together with `|`.
+///
+/// Absence of a flag means the opposite: reflective, glossy, positive, color media.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct HeaderAttributes(pub u64);
+
+impl HeaderAttributes {
+    /// Transparency media (otherwise reflective)
+    pub const TRANSPARENCY: HeaderAttributes = HeaderAttributes(1);
+    /// Matte media (otherwise glossy)
+    pub const MATTE: HeaderAttributes = HeaderAttributes(2);
+    /// Negative media (otherwise positive)
+    pub const NEGATIVE: HeaderAttributes = HeaderAttributes(4);
+    /// Black & white media (otherwise color)
+    pub const BLACK_AND_WHITE: HeaderAttributes = HeaderAttributes(8);
+
+    #[inline]
+    #[must_use]
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl ops::BitOr for HeaderAttributes {
+    type Output = Self;
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+/// Fields of the ICC profile header, see `Profile::header()`.
+///
+/// The platform and PCS illuminant fields are not included, because LCMS doesn't keep them:
+/// it always writes its own platform and the D50 illuminant required by the ICC spec.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProfileHeader {
+    pub version: IccVersion,
+    pub device_class: ProfileClassSignature,
+    pub color_space: ColorSpaceSignature,
+    pub pcs: ColorSpaceSignature,
+    pub rendering_intent: Intent,
+    /// Read-only. LCMS sets it when the profile is created.
+    pub created: Option<DateTime>,
+    pub flags: HeaderFlags,
+    pub attributes: HeaderAttributes,
+    pub manufacturer: u32,
+    pub model: u32,
+    /// Read-only. LCMS writes its own signature when saving.
+    pub creator: u32,
+    /// MD5 of the profile, or all zeros if not computed
+    pub profile_id: [u8; 16],
+}
+
+#[test]
+fn tm() {
+    let date = DateTime { year: 2024, month: 2, day: 29, hour: 23, minute: 59, second: 1 };
+    assert_eq!(Some(date), Tm::new(&date).date_time());
+    assert_eq!(None, Tm::default().date_time());
+    assert_eq!("2024-02-29 23:59:01", date.to_string());
+}
+
+#[test]
+fn icc_version() {
+    let v = IccVersion::from_encoded(0x0430_0000);
+    assert_eq!(IccVersion::V4_3, v);
+    assert_eq!(0x0430_0000, v.encoded());
+    assert_eq!("4.3.0", v.to_string());
+    assert_eq!(0x0210_0000, IccVersion::V2_1.encoded());
+    assert_eq!(IccVersion::new(2, 4, 1), IccVersion::from_encoded(0x0241_0000));
+    assert_eq!(IccVersion::new(12, 0, 0), IccVersion::from_encoded(IccVersion::new(12, 0, 0).encoded()));
+    assert!(IccVersion::V2_1 < IccVersion::new(2, 4, 0));
+    assert!(IccVersion::new(2, 4, 9) < IccVersion::V4_3);
+}
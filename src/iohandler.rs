@@ -0,0 +1,166 @@
+use crate::*;
+use std::io;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::os::raw::{c_char, c_void};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+
+/// Last IO error of the stream. LCMS can only report that something has failed, so the actual error is kept here.
+pub(crate) type ErrorSlot = Arc<Mutex<Option<io::Error>>>;
+
+/// `cmsIOHANDLER` from `lcms2_plugin.h` (`ffi::IOHANDLER` is opaque)
+#[repr(C)]
+struct IoHandler {
+    stream: *mut c_void,
+    context_id: ffi::Context,
+    used_space: u32,
+    reported_size: u32,
+    physical_file: [c_char; 256],
+    read: Option<unsafe extern "C" fn(*mut IoHandler, *mut c_void, u32, u32) -> u32>,
+    seek: Option<unsafe extern "C" fn(*mut IoHandler, u32) -> ffi::Bool>,
+    close: Option<unsafe extern "C" fn(*mut IoHandler) -> ffi::Bool>,
+    tell: Option<unsafe extern "C" fn(*mut IoHandler) -> u32>,
+    write: Option<unsafe extern "C" fn(*mut IoHandler, u32, *const c_void) -> ffi::Bool>,
+}
+
+/// Rust stream behind the `IoHandler`. LCMS positions are relative to `base`.
+struct Stream<S> {
+    inner: S,
+    base: u64,
+    error: ErrorSlot,
+}
+
+impl<S> Stream<S> {
+    /// Runs the IO operation, remembering its error. Panics can't unwind into C, so they're errors too.
+    fn run<T>(&mut self, op: impl FnOnce(&mut S) -> io::Result<T>) -> Option<T> {
+        let res = catch_unwind(AssertUnwindSafe(|| op(&mut self.inner)))
+            .unwrap_or_else(|_| Err(io::Error::other("panic in the stream")));
+        match res {
+            Ok(res) => Some(res),
+            Err(err) => {
+                if let Ok(mut slot) = self.error.lock() {
+                    *slot = Some(err);
+                }
+                None
+            },
+        }
+    }
+}
+
+#[inline]
+unsafe fn stream<'a, S>(io: *mut IoHandler) -> &'a mut Stream<S> {
+    &mut *(*io).stream.cast::<Stream<S>>()
+}
+
+unsafe extern "C" fn read<S: Read>(io: *mut IoHandler, buffer: *mut c_void, size: u32, count: u32) -> u32 {
+    let buffer = std::slice::from_raw_parts_mut(buffer.cast::<u8>(), size as usize * count as usize);
+    match stream::<S>(io).run(|s| s.read_exact(buffer)) {
+        Some(()) => count,
+        None => 0,
+    }
+}
+
+unsafe extern "C" fn seek<S: Seek>(io: *mut IoHandler, offset: u32) -> ffi::Bool {
+    let stream = stream::<S>(io);
+    let pos = stream.base + u64::from(offset);
+    ffi::Bool::from(stream.run(|s| s.seek(SeekFrom::Start(pos))).is_some())
+}
+
+unsafe extern "C" fn tell<S: Seek>(io: *mut IoHandler) -> u32 {
+    let stream = stream::<S>(io);
+    let base = stream.base;
+    stream.run(|s| s.stream_position()).map_or(0, |pos| pos.saturating_sub(base) as u32)
+}
+
+unsafe extern "C" fn close<S>(io: *mut IoHandler) -> ffi::Bool {
+    drop(Box::from_raw((*io).stream.cast::<Stream<S>>()));
+    drop(Box::from_raw(io));
+    1
+}
+
+unsafe extern "C" fn write<S: Write + Seek>(io: *mut IoHandler, size: u32, buffer: *const c_void) -> ffi::Bool {
+    let buffer = std::slice::from_raw_parts(buffer.cast::<u8>(), size as usize);
+    let stream = stream::<S>(io);
+    let base = stream.base;
+    match stream.run(|s| { s.write_all(buffer)?; s.stream_position() }) {
+        Some(pos) => {
+            // LCMS seeks back to fill in offsets, so the end is the furthest point written
+            let used = pos.saturating_sub(base) as u32;
+            if used > (*io).used_space {
+                (*io).used_space = used;
+            }
+            1
+        },
+        None => 0,
+    }
+}
+
+fn new_handler<S: Seek>(context: ffi::Context, stream: Stream<S>, size: u32) -> Box<IoHandler> {
+    Box::new(IoHandler {
+        stream: Box::into_raw(Box::new(stream)).cast(),
+        context_id: context,
+        used_space: 0,
+        reported_size: size,
+        physical_file: [0; 256],
+        read: None,
+        seek: Some(seek::<S>),
+        close: Some(close::<S>),
+        tell: Some(tell::<S>),
+        write: None,
+    })
+}
+
+/// Creates an IO handler that LCMS will read the profile from, starting at the current position of the reader.
+///
+/// LCMS owns the handler and closes it together with the profile, dropping the reader.
+/// The reader must stay valid for as long as the profile exists.
+pub(crate) fn new_reader<R: Read + Seek>(context: ffi::Context, mut reader: R) -> io::Result<(*mut ffi::IOHANDLER, ErrorSlot)> {
+    let base = reader.stream_position()?;
+    let end = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(base))?;
+    let size = u32::try_from(end.saturating_sub(base))
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "ICC profile is too large"))?;
+
+    let error = ErrorSlot::default();
+    let mut io = new_handler(context, Stream { inner: reader, base, error: error.clone() }, size);
+    io.read = Some(read::<R>);
+    Ok((Box::into_raw(io).cast(), error))
+}
+
+/// Lets LCMS write to the writer, starting at its current position. `save` gets a temporary IO handler and returns the number of bytes written, or 0 on failure.
+pub(crate) fn write_with<W: Write + Seek>(context: ffi::Context, writer: &mut W, save: impl FnOnce(*mut ffi::IOHANDLER) -> u32) -> io::Result<usize> {
+    let base = writer.stream_position()?;
+    let error = ErrorSlot::default();
+    let mut io = new_handler(context, Stream { inner: &mut *writer, base, error: error.clone() }, 0);
+    io.write = Some(write::<&mut W>);
+    let io = Box::into_raw(io);
+    let written = save(io.cast());
+    unsafe {
+        close::<&mut W>(io);
+    }
+    if written == 0 {
+        return Err(take_error(&error, "Unable to write ICC profile"));
+    }
+    writer.flush()?;
+    Ok(written as usize)
+}
+
+/// Reads everything LCMS has parsed the profile from, or `None` if the profile hasn't been read from an IO handler.
+pub(crate) unsafe fn read_all(io: *mut ffi::IOHANDLER) -> Option<Vec<u8>> {
+    let io = io.cast::<IoHandler>();
+    if io.is_null() {
+        return None;
+    }
+    let (read, seek) = ((*io).read?, (*io).seek?);
+    let mut data = vec![0u8; (*io).reported_size as usize];
+    if data.is_empty() || seek(io, 0) == 0 || read(io, data.as_mut_ptr().cast(), data.len() as u32, 1) != 1 {
+        return None;
+    }
+    Some(data)
+}
+
+/// Turns the failure reported by LCMS into the actual IO error, if there was one
+pub(crate) fn take_error(error: &ErrorSlot, fallback: &str) -> io::Error {
+    error.lock().ok().and_then(|mut e| e.take())
+        .unwrap_or_else(|| io::Error::new(io::ErrorKind::InvalidData, fallback))
+}
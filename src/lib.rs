@@ -11,34 +11,50 @@ extern crate lcms2_sys as ffi;
 extern crate foreign_types;
 
 mod profile;
+mod profilesequence;
+mod borrowedprofile;
 mod tag;
 mod ciecam;
+mod colorant;
 mod context;
+mod dict;
 mod mlu;
 mod namedcolorlist;
 mod pipeline;
+mod pixel;
 mod eval;
 mod ext;
 mod flags;
+mod header;
+mod iohandler;
 mod locale;
 mod stage;
 mod transform;
+mod transformbuilder;
 mod tonecurve;
 mod error;
 use std::marker::PhantomData;
 
 pub use crate::profile::*;
+pub use crate::profilesequence::*;
+pub use crate::borrowedprofile::*;
 pub use crate::error::*;
 pub use crate::ciecam::*;
+pub use crate::colorant::*;
 pub use crate::context::{GlobalContext, ThreadContext};
+pub use crate::dict::*;
 pub use crate::mlu::*;
 pub use crate::ext::*;
 pub use crate::flags::*;
+pub use crate::header::{DateTime, HeaderAttributes, HeaderFlags, IccVersion, ProfileHeader};
 pub use crate::locale::*;
 pub use crate::pipeline::*;
+pub use crate::pixel::*;
 pub use crate::stage::*;
 pub use crate::transform::*;
+pub use crate::transformbuilder::*;
 pub use crate::tonecurve::*;
+pub use crate::tag::{TagInfo, TagValue};
 pub use crate::namedcolorlist::*;
 
 pub use crate::ffi::CIEXYZ;
@@ -63,6 +79,8 @@ pub use crate::ffi::ViewingConditions;
 /// Value of a tag in an ICC profile
 pub enum Tag<'a> {
     CIExyYTRIPLE(&'a ffi::CIExyYTRIPLE),
+    /// 3x3 matrix of the `ChromaticAdaptationTag` or `ArgyllArtsTag`, row by row
+    Matrix(&'a [[f64; 3]; 3]),
     CIEXYZ(&'a ffi::CIEXYZ),
     ICCData(&'a ffi::ICCData),
     ICCMeasurementConditions(&'a ffi::ICCMeasurementConditions),
@@ -73,13 +91,24 @@ pub enum Tag<'a> {
     NamedColorList(&'a NamedColorListRef),
     Pipeline(&'a PipelineRef),
     Screening(&'a ffi::Screening),
-    SEQ(&'a ffi::SEQ),
+    /// Descriptions of profiles a device link was made from
+    SEQ(&'a ProfileSequenceRef),
     Intent(Intent),
     ColorimetricIntentImageState(ffi::ColorimetricIntentImageState),
     Technology(ffi::TechnologySignature),
     ToneCurve(&'a ToneCurveRef),
     UcrBg(&'a ffi::UcrBg),
     VcgtCurves([&'a ToneCurveRef; 3]),
+    /// Order of colorants, `0xFF` marks unused entries
+    ColorantOrder(&'a ColorantOrder),
+    /// Names of colorants of the device or of the output
+    ColorantTable(&'a ColorantTableRef),
+    DateTime(DateTime),
+    /// Coding-independent code points (`cicp`)
+    VideoSignalType(&'a ffi::VideoSignalType),
+    MHC2(&'a ffi::MHC2Type),
+    /// Name/value pairs of the `MetaTag`
+    Dict(&'a DictRef),
     /// Unknown format or missing data
     None,
 }
@@ -4,7 +4,7 @@ use std::fmt::Write;
 use std::os::raw::c_char;
 
 /// Language code from ISO-639/2 and region code from ISO-3166.
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Locale {
     language: [c_char; 3],
     country: [c_char; 3],
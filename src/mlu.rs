@@ -2,6 +2,7 @@ use crate::ffi::wchar_t;
 use crate::*;
 use foreign_types::{ForeignType, ForeignTypeRef};
 use std::char::{decode_utf16, REPLACEMENT_CHARACTER};
+use std::collections::HashMap;
 use std::ffi::CString;
 use std::fmt;
 use std::iter::repeat;
@@ -14,6 +15,7 @@ foreign_type! {
     pub unsafe type MLU {
         type CType = ffi::MLU;
         fn drop = ffi::cmsMLUfree;
+        fn clone = ffi::cmsMLUdup;
     }
 }
 
@@ -156,6 +158,14 @@ impl MLURef {
         out
     }
 
+    /// All translations with their text
+    #[must_use]
+    pub fn texts(&self) -> HashMap<Locale, String> {
+        self.tanslations().into_iter()
+            .filter_map(|locale| Some((locale, self.text(locale).ok()?)))
+            .collect()
+    }
+
     /// Obtains the translation rule for given multilocalized unicode object.
     pub fn tanslation(&self, locale: Locale) -> LCMSResult<Locale> {
         let mut out = Locale::none();
@@ -202,4 +212,12 @@ fn mlu() {
         Ok(CString::new("OK").unwrap()),
         m.text_ascii(Locale::none())
     );
+
+    let mut m = MLU::new(2);
+    assert!(m.set_text("Colour", Locale::new("en_GB")));
+    assert!(m.set_text("Farbe", Locale::new("de_DE")));
+    let texts = m.texts();
+    assert_eq!(2, texts.len());
+    assert_eq!("Colour", texts[&Locale::new("en_GB")]);
+    assert_eq!("Farbe", texts[&Locale::new("de_DE")]);
 }
@@ -20,6 +20,7 @@ foreign_type! {
     pub unsafe type NamedColorList {
         type CType = ffi::NAMEDCOLORLIST;
         fn drop = ffi::cmsFreeNamedColorList;
+        fn clone = ffi::cmsDupNamedColorList;
     }
 }
 
@@ -42,18 +43,28 @@ impl NamedColorList {
 impl NamedColorListRef {
     /// Number of colors in the palette
     #[inline]
-    fn len(&self) -> usize {
+    #[must_use]
+    pub fn len(&self) -> usize {
         unsafe { ffi::cmsNamedColorCount(self.as_ptr()) as usize }
     }
 
-    /// Find color by name
-    fn index_of(&self, color_name: &str) -> usize {
-        let s = CString::new(color_name).unwrap();
-        unsafe { ffi::cmsNamedColorIndex(self.as_ptr(), s.as_ptr()) as usize }
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Find index of a color by its name (without prefix and suffix)
+    #[must_use]
+    pub fn find(&self, color_name: &str) -> Option<usize> {
+        let s = CString::new(color_name).ok()?;
+        let index = unsafe { ffi::cmsNamedColorIndex(self.as_ptr(), s.as_ptr()) };
+        usize::try_from(index).ok()
     }
 
     /// Get color info
-    fn get(&self, index: usize) -> Option<NamedColorInfo> {
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<NamedColorInfo> {
         let mut name = [0 as c_char; 256];
         let mut prefix = [0 as c_char; 33];
         let mut suffix = [0 as c_char; 33];
@@ -84,19 +95,80 @@ impl NamedColorListRef {
         }
     }
 
-    fn colors(&self) -> Vec<NamedColorInfo> {
-        (0..self.len()).filter_map(|i| self.get(i)).collect()
+    /// All colors in the palette
+    #[must_use]
+    pub fn colors(&self) -> Vec<NamedColorInfo> {
+        self.iter().collect()
     }
 
-    /// Push a color at the end of the palette
-    fn append(&mut self, color_name: &str, mut pcs: [u16; 3], mut colorant: [u16; ffi::MAXCHANNELS]) -> bool {
-        let s = CString::new(color_name).unwrap();
-        unsafe {
-            0 != ffi::cmsAppendNamedColor(self.as_ptr(), s.as_ptr(), pcs.as_mut_ptr(), colorant.as_mut_ptr())
+    /// Iterate over all colors in the palette
+    #[inline]
+    #[must_use]
+    pub fn iter(&self) -> NamedColorIter<'_> {
+        NamedColorIter { list: self, index: 0 }
+    }
+
+    /// Prefix of color names in the palette, e.g. "PANTONE ".
+    ///
+    /// LCMS stores it once for the whole palette, but returns it only along with a color, so it's `None` when the palette is empty.
+    #[must_use]
+    pub fn prefix(&self) -> Option<String> {
+        self.get(0).map(|c| c.prefix)
+    }
+
+    /// Suffix of color names in the palette, e.g. " CVC".
+    ///
+    /// LCMS stores it once for the whole palette, but returns it only along with a color, so it's `None` when the palette is empty.
+    #[must_use]
+    pub fn suffix(&self) -> Option<String> {
+        self.get(0).map(|c| c.suffix)
+    }
+
+    /// Push a color at the end of the palette.
+    ///
+    /// `pcs` is the color encoded as 16-bit Lab, and `colorant` has device values of the color (unused channels set to 0).
+    pub fn append(&mut self, color_name: &str, mut pcs: [u16; 3], mut colorant: [u16; ffi::MAXCHANNELS]) -> LCMSResult<()> {
+        let s = CString::new(color_name).map_err(|_| Error::InvalidString)?;
+        if 0 != unsafe { ffi::cmsAppendNamedColor(self.as_ptr(), s.as_ptr(), pcs.as_mut_ptr(), colorant.as_mut_ptr()) } {
+            Ok(())
+        } else {
+            Err(Error::ObjectCreationError)
         }
     }
 }
 
+/// Iterator over colors of `NamedColorListRef`
+pub struct NamedColorIter<'a> {
+    list: &'a NamedColorListRef,
+    index: usize,
+}
+
+impl<'a> Iterator for NamedColorIter<'a> {
+    type Item = NamedColorInfo;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let color = self.list.get(self.index)?;
+        self.index += 1;
+        Some(color)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.list.len().saturating_sub(self.index);
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for NamedColorIter<'a> {}
+
+impl<'a> IntoIterator for &'a NamedColorListRef {
+    type Item = NamedColorInfo;
+    type IntoIter = NamedColorIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<'a> fmt::Debug for NamedColorListRef {
     #[cold]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -111,11 +183,24 @@ impl<'a> fmt::Debug for NamedColorListRef {
 #[test]
 fn named() {
     let mut n = NamedColorList::new(10, 3, "hello", "world").unwrap();
-    assert!(n.append("yellow", [1,2,3], [1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16]));
+    assert!(n.is_empty());
+    assert_eq!(None, n.prefix());
+    n.append("yellow", [1,2,3], [1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16]).unwrap();
     assert_eq!(None, n.get(10000));
 
     let c = n.get(0).unwrap();
     assert_eq!("yellow", c.name);
     assert_eq!("hello", c.prefix);
     assert_eq!([1,2,3], c.pcs);
+
+    n.append("blue", [4,5,6], [0; 16]).unwrap();
+    let copy = n.to_owned();
+    assert_eq!(2, copy.len());
+    assert_eq!(Some(1), copy.find("blue"));
+    assert_eq!(None, copy.find("green"));
+    assert_eq!(Some("world".to_owned()), copy.suffix());
+    assert_eq!(vec!["yellow", "blue"], copy.iter().map(|c| c.name).collect::<Vec<_>>());
+    let mut iter = copy.iter();
+    iter.next();
+    assert_eq!(1, iter.len());
 }
@@ -0,0 +1,140 @@
+use crate::*;
+
+/// A pixel type that knows its own memory layout, so it can be used with `Transform::new_typed()`
+/// without specifying a `PixelFormat`.
+///
+/// # Safety
+///
+/// `FORMAT` must exactly describe the memory layout of the type: the number and order of channels, their size, and extra channels.
+/// LCMS will read and write the pixels based on it.
+pub unsafe trait Pixel: Copy {
+    /// Layout of the pixel in LCMS terms
+    const FORMAT: PixelFormat;
+}
+
+/// Single-channel gray pixel
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Gray<T> {
+    pub v: T,
+}
+
+/// Gray pixel with an alpha channel. Alpha is not color-managed.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GrayAlpha<T> {
+    pub v: T,
+    pub a: T,
+}
+
+/// RGB pixel
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rgb<T> {
+    pub r: T,
+    pub g: T,
+    pub b: T,
+}
+
+/// RGB pixel with an alpha channel. Alpha is not color-managed.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rgba<T> {
+    pub r: T,
+    pub g: T,
+    pub b: T,
+    pub a: T,
+}
+
+/// RGB pixel stored in reverse order
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Bgr<T> {
+    pub b: T,
+    pub g: T,
+    pub r: T,
+}
+
+/// RGB pixel stored in reverse order, followed by alpha. Alpha is not color-managed.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Bgra<T> {
+    pub b: T,
+    pub g: T,
+    pub r: T,
+    pub a: T,
+}
+
+/// CMYK pixel
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Cmyk<T> {
+    pub c: T,
+    pub m: T,
+    pub y: T,
+    pub k: T,
+}
+
+macro_rules! pixel_formats {
+    ($($ty:ty => $format:ident,)*) => {
+        $(
+            unsafe impl Pixel for $ty {
+                const FORMAT: PixelFormat = PixelFormat::$format;
+            }
+        )*
+    };
+}
+
+pixel_formats! {
+    Gray<u8> => GRAY_8,
+    Gray<u16> => GRAY_16,
+    Gray<f32> => GRAY_FLT,
+    Gray<f64> => GRAY_DBL,
+    GrayAlpha<u8> => GRAYA_8,
+    GrayAlpha<u16> => GRAYA_16,
+    Rgb<u8> => RGB_8,
+    Rgb<u16> => RGB_16,
+    Rgb<f32> => RGB_FLT,
+    Rgb<f64> => RGB_DBL,
+    Rgba<u8> => RGBA_8,
+    Rgba<u16> => RGBA_16,
+    Rgba<f32> => RGBA_FLT,
+    Bgr<u8> => BGR_8,
+    Bgr<u16> => BGR_16,
+    Bgr<f32> => BGR_FLT,
+    Bgr<f64> => BGR_DBL,
+    Bgra<u8> => BGRA_8,
+    Bgra<u16> => BGRA_16,
+    Bgra<f32> => BGRA_FLT,
+    Cmyk<u8> => CMYK_8,
+    Cmyk<u16> => CMYK_16,
+    Cmyk<f32> => CMYK_FLT,
+    Cmyk<f64> => CMYK_DBL,
+    CIELab => Lab_DBL,
+    CIEXYZ => XYZ_DBL,
+}
+
+/// LCMS color space number (`PT_*`) of the pixel format
+#[inline]
+pub(crate) fn format_color_space(format: PixelFormat) -> u32 {
+    (format.0 >> 16) & 31
+}
+
+#[test]
+fn pixel_sizes() {
+    fn check<P: Pixel>() {
+        assert_eq!(P::FORMAT.bytes_per_pixel(), std::mem::size_of::<P>(), "{}", std::any::type_name::<P>());
+    }
+    check::<Gray<u8>>();
+    check::<Gray<f64>>();
+    check::<GrayAlpha<u16>>();
+    check::<Rgb<u8>>();
+    check::<Rgb<f32>>();
+    check::<Rgba<u16>>();
+    check::<Bgr<f64>>();
+    check::<Bgra<u8>>();
+    check::<Cmyk<u16>>();
+    check::<Cmyk<f32>>();
+    check::<CIELab>();
+    check::<CIEXYZ>();
+}
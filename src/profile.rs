@@ -1,11 +1,13 @@
 use crate::context::Context;
 use crate::*;
+use crate::header::Tm;
 use foreign_types::ForeignTypeRef;
+use std::collections::HashMap;
 use std::default::Default;
 use std::fmt;
 use std::fs::File;
 use std::io;
-use std::io::Read;
+use std::io::{Read, Seek, Write};
 use std::os::raw::c_void;
 use std::path::Path;
 use std::ptr;
@@ -34,6 +36,15 @@ impl Profile<GlobalContext> {
         Self::new_file_context(GlobalContext::new(), path)
     }
 
+    /// Parse ICC profile from a stream, starting at its current position (e.g. a profile embedded in a larger file).
+    ///
+    /// The whole profile is copied from the stream into memory here, so IO errors are reported by this function,
+    /// and the stream isn't kept. Tags are copied as they are, and parsed only when they're used.
+    #[inline]
+    pub fn from_reader<R: Read + Seek>(reader: R) -> io::Result<Self> {
+        Self::from_reader_context(GlobalContext::new(), reader)
+    }
+
     /// Create an ICC virtual profile for sRGB space. sRGB is a standard RGB color space created cooperatively by HP and Microsoft in 1996 for use on monitors, printers, and the Internet.
     #[inline]
     #[must_use]
@@ -130,6 +141,147 @@ impl<Ctx: Context> Profile<Ctx> {
         }
     }
 
+    /// Makes a deep copy of the profile and all of its tags, in the same context.
+    ///
+    /// Tags known to LCMS are copied as parsed objects, and other tags as raw data. Linked tags stay linked.
+    /// A copy of a profile loaded from ICC data keeps that data too, so that `verify_profile_id()` gives the same answer for both.
+    /// Profiles created in memory don't have a creation date to copy (LCMS sets it to the current time).
+    pub fn try_clone(&self) -> LCMSResult<Self> {
+        let mut copy = match self.original_icc() {
+            Some(icc) => self.parse_in_context(&icc)?,
+            None => Self::new_handle(unsafe {
+                ffi::cmsCreateProfilePlaceholder(ffi::cmsGetProfileContextID(self.handle))
+            })?,
+        };
+        copy.set_header(&self.header());
+
+        let sigs = self.tag_signatures();
+        for sig in copy.tag_signatures() {
+            if !sigs.contains(&sig) && !copy.remove_tag(sig) {
+                return Err(Error::ObjectCreationError);
+            }
+        }
+        let mut has_raw_tags = false;
+        for &sig in &sigs {
+            let ok = if let Some(dest) = self.tag_linked_to(sig, &sigs) {
+                copy.link_tag(sig, dest)
+            } else {
+                let tag = unsafe { ffi::cmsReadTag(self.handle, sig) };
+                if !tag.is_null() {
+                    0 != unsafe { ffi::cmsWriteTag(copy.handle, sig, tag) }
+                } else {
+                    let data = self.read_raw_tag(sig).ok_or(Error::MissingData)?;
+                    if copy.read_raw_tag(sig).as_ref() == Some(&data) {
+                        continue;
+                    }
+                    has_raw_tags = true;
+                    0 != unsafe { ffi::cmsWriteRawTag(copy.handle, sig, data.as_ptr().cast(), data.len() as u32) }
+                }
+            };
+            if !ok {
+                return Err(Error::ObjectCreationError);
+            }
+        }
+        if has_raw_tags {
+            copy.reload()?;
+        }
+        Ok(copy)
+    }
+
+    /// Replaces the profile with a freshly parsed copy of its serialized form.
+    ///
+    /// LCMS keeps tags written with `cmsWriteRawTag` in a state where reading them with `cmsReadTag`
+    /// destroys them (and then `cmsReadRawTag` crashes), so such tags must not stay in memory.
+    fn reload(&mut self) -> LCMSResult<()> {
+        *self = self.reparsed()?;
+        Ok(())
+    }
+
+    /// Parses a copy of the profile from its serialized form, in the same context
+    fn reparsed(&self) -> LCMSResult<Self> {
+        self.parse_in_context(&self.icc()?)
+    }
+
+    /// Parses ICC data as a new profile in the context of this one
+    fn parse_in_context(&self, icc: &[u8]) -> LCMSResult<Self> {
+        Self::new_handle(unsafe {
+            ffi::cmsOpenProfileFromMemTHR(ffi::cmsGetProfileContextID(self.handle), icc.as_ptr().cast::<c_void>(), icc.len() as u32)
+        })
+    }
+
+    /// The ICC data the profile has been loaded from, if any.
+    ///
+    /// LCMS serializes tags it has parsed again, which can change their bytes, so `icc()` can't be used to get the original data.
+    fn original_icc(&self) -> Option<Vec<u8>> {
+        unsafe { iohandler::read_all(ffi::cmsGetProfileIOhandler(self.handle)) }
+    }
+
+    /// Reads the tag as raw bytes, exactly as stored in the ICC file (starting with the tag type signature).
+    ///
+    /// This works for any tag, including private tags of types unknown to LCMS. Tags that have been modified are serialized again.
+    #[must_use]
+    pub fn read_raw_tag(&self, sig: TagSignature) -> Option<Vec<u8>> {
+        unsafe {
+            let size = ffi::cmsReadRawTag(self.handle, sig, ptr::null_mut(), 0);
+            if size == 0 {
+                return None;
+            }
+            let mut data = vec![0u8; size as usize];
+            let size = ffi::cmsReadRawTag(self.handle, sig, data.as_mut_ptr().cast(), size);
+            if size == 0 {
+                return None;
+            }
+            data.truncate(size as usize);
+            Some(data)
+        }
+    }
+
+    /// Writes the tag as raw bytes, which must include the tag type signature. The data is stored as-is.
+    ///
+    /// To keep the profile unchanged on failure and make the tag readable with `read_tag()`, every raw write
+    /// serializes the whole profile and parses it again, twice. This is slow for large profiles.
+    pub fn write_raw_tag(&mut self, sig: TagSignature, data: &[u8]) -> LCMSResult<()> {
+        // The tag is written to a copy, because a failed reload would leave the tag in an unusable state
+        let mut copy = self.reparsed()?;
+        if 0 == unsafe { ffi::cmsWriteRawTag(copy.handle, sig, data.as_ptr().cast(), data.len() as u32) } {
+            return Err(Error::ObjectCreationError);
+        }
+        copy.reload()?;
+        *self = copy;
+        Ok(())
+    }
+
+    /// Finds the tag that `sig` is linked to, among `sigs` from `tag_signatures()`
+    fn tag_linked_to(&self, sig: TagSignature, sigs: &[TagSignature]) -> Option<TagSignature> {
+        // LCMS returns 0 for unlinked tags, which isn't a valid `TagSignature`, so the result is read as the `u32` it's represented by
+        let linked = unsafe {
+            let tag_linked_to: unsafe extern "C" fn(ffi::HPROFILE, TagSignature) -> u32 =
+                std::mem::transmute(ffi::cmsTagLinkedTo as unsafe extern "C" fn(ffi::HPROFILE, TagSignature) -> TagSignature);
+            tag_linked_to(self.handle, sig)
+        };
+        if linked == 0 {
+            return None;
+        }
+        sigs.iter().copied().find(|&s| s as u32 == linked)
+    }
+
+    /// Write the ICC file to the stream, starting at its current position, without buffering the whole profile in memory.
+    ///
+    /// LCMS goes back to fill in offsets of some tags, so the stream has to be seekable.
+    /// Returns the number of bytes written.
+    pub fn write_to<W: Write + Seek>(&self, mut writer: W) -> io::Result<usize> {
+        unsafe {
+            iohandler::write_with(ffi::cmsGetProfileContextID(self.handle), &mut writer, |io| {
+                ffi::cmsSaveProfileToIOhandler(self.handle, io)
+            })
+        }
+    }
+
+    /// Save the ICC file to disk. Returns the number of bytes written.
+    pub fn save_file<P: AsRef<Path>>(&self, path: P) -> io::Result<usize> {
+        self.write_to(io::BufWriter::new(File::create(path)?))
+    }
+
     /// Gets the device class signature from profile header.
     #[inline]
     #[must_use]
@@ -150,11 +302,55 @@ impl<Ctx: Context> Profile<Ctx> {
         unsafe { ffi::cmsGetEncodedICCversion(self.handle) }
     }
 
+    /// Sets the profile ICC version in the same format as it is stored in the header.
     #[inline]
-    pub fn set_encoded_icc_version(&self, v: u32) {
+    pub fn set_encoded_icc_version(&mut self, v: u32) {
         unsafe { ffi::cmsSetEncodedICCversion(self.handle, v) }
     }
 
+    /// Returns the profile ICC version
+    #[inline]
+    #[must_use]
+    pub fn icc_version(&self) -> IccVersion {
+        IccVersion::from_encoded(self.encoded_icc_version())
+    }
+
+    /// Sets the ICC version in the header. This doesn't change types of the tags, see `convert_to_version()`.
+    #[inline]
+    pub fn set_icc_version(&mut self, version: IccVersion) {
+        self.set_encoded_icc_version(version.encoded());
+    }
+
+    /// Changes the ICC version, and rewrites all tags using types appropriate for that version
+    /// (e.g. `desc` instead of `mluc` for descriptions, and `curv` instead of `para` for curves in v2).
+    ///
+    /// Tags of types unknown to LCMS are kept as-is. The profile is unchanged if the conversion fails.
+    pub fn convert_to_version(&mut self, version: IccVersion) -> LCMSResult<()> {
+        // Tags can't be rewritten from their own data, so they're read from one copy and written to another
+        let source = self.reparsed()?;
+        let mut converted = self.reparsed()?;
+        converted.set_icc_version(version);
+
+        let sigs = source.tag_signatures();
+        for &sig in &sigs {
+            let ok = if let Some(dest) = source.tag_linked_to(sig, &sigs) {
+                converted.link_tag(sig, dest)
+            } else {
+                let data = unsafe { ffi::cmsReadTag(source.handle, sig) };
+                if data.is_null() {
+                    continue;
+                }
+                0 != unsafe { ffi::cmsWriteTag(converted.handle, sig, data) }
+            };
+            if !ok {
+                return Err(Error::ObjectCreationError);
+            }
+        }
+        converted.reload()?;
+        *self = converted;
+        Ok(())
+    }
+
     /// Gets the attribute flags. Currently defined values correspond to the low 4 bytes of the 8 byte attribute quantity.
     ///
     ///  * `Reflective`
@@ -306,6 +502,66 @@ impl<Ctx: Context> Profile<Ctx> {
             .collect())
     }
 
+    /// All translations of the profile description (`ProfileDescriptionTag`).
+    ///
+    /// Unlike `info()`, this doesn't pick one locale. Empty if the tag is missing.
+    #[must_use]
+    pub fn description(&self) -> HashMap<Locale, String> {
+        self.localized_texts(TagSignature::ProfileDescriptionTag)
+    }
+
+    /// All translations of the copyright notice (`CopyrightTag`)
+    #[must_use]
+    pub fn copyright(&self) -> HashMap<Locale, String> {
+        self.localized_texts(TagSignature::CopyrightTag)
+    }
+
+    /// All translations of the device manufacturer description (`DeviceMfgDescTag`)
+    #[must_use]
+    pub fn manufacturer(&self) -> HashMap<Locale, String> {
+        self.localized_texts(TagSignature::DeviceMfgDescTag)
+    }
+
+    /// All translations of the device model description (`DeviceModelDescTag`)
+    #[must_use]
+    pub fn model(&self) -> HashMap<Locale, String> {
+        self.localized_texts(TagSignature::DeviceModelDescTag)
+    }
+
+    /// Sets the profile description.
+    ///
+    /// LCMS stores it as `mluc` in V4 profiles and as `desc` in V2 profiles, so set the version first.
+    /// V2 profiles can keep only one translation.
+    #[inline]
+    pub fn set_description(&mut self, text: &MLURef) -> LCMSResult<()> {
+        self.write_tag(TagSignature::ProfileDescriptionTag, Tag::MLU(text))
+    }
+
+    /// Sets the copyright notice. V2 profiles store it as plain `text`, with only one translation.
+    #[inline]
+    pub fn set_copyright(&mut self, text: &MLURef) -> LCMSResult<()> {
+        self.write_tag(TagSignature::CopyrightTag, Tag::MLU(text))
+    }
+
+    /// Sets the device manufacturer description. Stored as `mluc` in V4 and `desc` in V2 profiles.
+    #[inline]
+    pub fn set_manufacturer(&mut self, text: &MLURef) -> LCMSResult<()> {
+        self.write_tag(TagSignature::DeviceMfgDescTag, Tag::MLU(text))
+    }
+
+    /// Sets the device model description. Stored as `mluc` in V4 and `desc` in V2 profiles.
+    #[inline]
+    pub fn set_model(&mut self, text: &MLURef) -> LCMSResult<()> {
+        self.write_tag(TagSignature::DeviceModelDescTag, Tag::MLU(text))
+    }
+
+    fn localized_texts(&self, sig: TagSignature) -> HashMap<Locale, String> {
+        match self.read_tag(sig) {
+            Tag::MLU(mlu) => mlu.texts(),
+            _ => HashMap::new(),
+        }
+    }
+
     /// Returns the profile ICC version. The version is decoded to readable floating point format.
     #[inline]
     #[must_use]
@@ -331,6 +587,28 @@ impl<Ctx: Context> Profile<Ctx> {
         }
     }
 
+    /// Lists tags in the profile, with their types, sizes and links between them.
+    ///
+    /// LCMS doesn't expose types of tags, so they're read from the raw data. Tags that have been read or written
+    /// are serialized again for this, which can be slow for large tags (e.g. lookup tables).
+    pub fn tags(&self) -> impl Iterator<Item = TagInfo> + '_ {
+        let sigs = self.tag_signatures();
+        let all = sigs.clone();
+        sigs.into_iter().map(move |signature| {
+            let mut type_signature = [0u8; 4];
+            let size = unsafe { ffi::cmsReadRawTag(self.handle, signature, ptr::null_mut(), 0) };
+            if size >= 4 {
+                unsafe { ffi::cmsReadRawTag(self.handle, signature, type_signature.as_mut_ptr().cast(), 4) };
+            }
+            TagInfo {
+                signature,
+                type_signature: u32::from_be_bytes(type_signature),
+                size: size as usize,
+                linked_to: self.tag_linked_to(signature, &all),
+            }
+        })
+    }
+
     #[inline]
     #[must_use]
     pub fn detect_black_point(&self, intent: Intent) -> Option<CIEXYZ> {
@@ -408,11 +686,25 @@ impl<Ctx: Context> Profile<Ctx> {
         unsafe { Tag::new(sig, ffi::cmsReadTag(self.handle, sig) as *const u8) }
     }
 
+    /// Writes the tag. Fails if the data has a different type than LCMS uses for tags with this signature.
     #[inline]
-    pub fn write_tag(&mut self, sig: TagSignature, tag: Tag<'_>) -> bool {
-        unsafe {
-            ffi::cmsWriteTag(self.handle, sig, tag.data_for_signature(sig).cast()) != 0
-        }
+    pub fn write_tag(&mut self, sig: TagSignature, tag: Tag<'_>) -> LCMSResult<()> {
+        unsafe { tag.write_to(self.handle, sig) }
+    }
+
+    /// Reads a tag and copies its data, so that it can outlive the profile or be written to another profile.
+    ///
+    /// Returns `None` if the tag is missing, or its type can't be copied (see `Tag::to_value()`).
+    #[must_use]
+    pub fn read_tag_owned(&self, sig: TagSignature) -> Option<TagValue> {
+        self.read_tag(sig).to_value()
+    }
+
+    /// Writes the tag, checking first that the value has the type LCMS uses for this signature.
+    ///
+    /// Tags unknown to LCMS can only be written with `write_raw_tag()`.
+    pub fn set_tag(&mut self, sig: TagSignature, value: TagValue) -> LCMSResult<()> {
+        self.write_tag(sig, value.as_tag())
     }
 
     #[inline]
@@ -454,6 +746,88 @@ impl<Ctx: Context> Profile<Ctx> {
             ffi::cmsSetHeaderProfileID(self.handle, &id as *const ffi::ProfileID as *mut _);
         }
     }
+
+    /// Computes the MD5 profile ID, as defined by the ICC spec, without storing it in the header.
+    ///
+    /// For profiles loaded from ICC data, the ID is computed from the loaded tags and the current header,
+    /// so it doesn't depend on which tags have been read (LCMS serializes parsed tags again, which can change their bytes).
+    /// Tags written since loading are not included. Profiles created in memory are hashed as `icc()` saves them.
+    ///
+    /// Use `set_default_profile_id()` to compute and store the ID of the profile as it is going to be saved.
+    pub fn compute_profile_id(&self) -> LCMSResult<[u8; 16]> {
+        let mut copy = match self.original_icc() {
+            Some(icc) => self.parse_in_context(&icc)?,
+            None => self.reparsed()?,
+        };
+        copy.set_header(&self.header());
+        if 0 == unsafe { ffi::cmsMD5computeID(copy.handle) } {
+            return Err(Error::ObjectCreationError);
+        }
+        Ok(copy.profile_id_bytes())
+    }
+
+    /// Checks whether the profile ID stored in the header matches the profile's content.
+    ///
+    /// Returns `Error::MissingData` if the profile has no ID (it's all zeros, which the ICC spec allows).
+    pub fn verify_profile_id(&self) -> LCMSResult<bool> {
+        let stored = self.profile_id_bytes();
+        if stored == [0; 16] {
+            return Err(Error::MissingData);
+        }
+        Ok(stored == self.compute_profile_id()?)
+    }
+
+    #[inline]
+    fn profile_id_bytes(&self) -> [u8; 16] {
+        unsafe { std::mem::transmute::<ffi::ProfileID, [u8; 16]>(self.profile_id()) }
+    }
+
+    /// Gets the date and time when the profile was created, according to its header
+    #[must_use]
+    pub fn creation_date_time(&self) -> Option<DateTime> {
+        let mut tm = Tm::default();
+        unsafe {
+            if ffi::cmsGetHeaderCreationDateTime(self.handle, (&mut tm as *mut Tm).cast()) == 0 {
+                return None;
+            }
+        }
+        tm.date_time()
+    }
+
+    /// Reads all fields of the profile header at once
+    #[must_use]
+    pub fn header(&self) -> ProfileHeader {
+        ProfileHeader {
+            version: self.icc_version(),
+            device_class: self.device_class(),
+            color_space: self.color_space(),
+            pcs: self.pcs(),
+            rendering_intent: self.header_rendering_intent(),
+            created: self.creation_date_time(),
+            flags: HeaderFlags(self.header_flags()),
+            attributes: HeaderAttributes(self.header_attributes()),
+            manufacturer: self.header_manufacturer(),
+            model: self.header_model(),
+            creator: self.header_creator(),
+            profile_id: self.profile_id_bytes(),
+        }
+    }
+
+    /// Sets all writable fields of the profile header. `created` and `creator` are ignored.
+    pub fn set_header(&mut self, header: &ProfileHeader) {
+        self.set_icc_version(header.version);
+        self.set_device_class(header.device_class);
+        self.set_color_space(header.color_space);
+        self.set_pcs(header.pcs);
+        self.set_header_rendering_intent(header.rendering_intent);
+        self.set_header_flags(header.flags.0);
+        self.set_header_attributes(header.attributes.0);
+        unsafe {
+            ffi::cmsSetHeaderManufacturer(self.handle, header.manufacturer);
+            ffi::cmsSetHeaderModel(self.handle, header.model);
+            self.set_profile_id(std::mem::transmute::<[u8; 16], ffi::ProfileID>(header.profile_id));
+        }
+    }
 }
 
 /// Per-context functions that can be used with a `ThreadContext`
@@ -468,6 +842,30 @@ impl<Ctx: Context> Profile<Ctx> {
         })
     }
 
+    /// See `from_reader()`
+    pub fn from_reader_context<R: Read + Seek>(context: impl AsRef<Ctx>, reader: R) -> io::Result<Self> {
+        let (profile, error) = unsafe { Self::new_reader_context(context, reader)? };
+        // LCMS copies tags that haven't been parsed from the stream, and fails if reading them fails
+        let mut icc = io::Cursor::new(Vec::new());
+        profile.write_to(&mut icc).map_err(|_| iohandler::take_error(&error, "ICC profile has an invalid tag"))?;
+        let icc = icc.into_inner();
+        Self::new_handle(unsafe {
+            ffi::cmsOpenProfileFromMemTHR(ffi::cmsGetProfileContextID(profile.handle), icc.as_ptr().cast::<c_void>(), icc.len() as u32)
+        }).map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid ICC profile"))
+    }
+
+    /// Opens the profile without reading any tags yet.
+    ///
+    /// The reader must outlive the profile.
+    pub(crate) unsafe fn new_reader_context<R: Read + Seek>(context: impl AsRef<Ctx>, reader: R) -> io::Result<(Self, iohandler::ErrorSlot)> {
+        let context = context.as_ref().as_ptr();
+        let (io, error) = iohandler::new_reader(context, reader)?;
+        match Self::new_handle(ffi::cmsOpenProfileFromIOhandlerTHR(context, io)) {
+            Ok(profile) => Ok((profile, error)),
+            Err(_) => Err(iohandler::take_error(&error, "Invalid ICC profile")),
+        }
+    }
+
     #[inline]
     pub fn new_file_context<P: AsRef<Path>>(context: impl AsRef<Ctx>, path: P) -> io::Result<Self> {
         let mut buf = Vec::new();
@@ -575,6 +973,16 @@ impl<Ctx: Context> Profile<Ctx> {
     }
 }
 
+impl<Ctx: Context> Clone for Profile<Ctx> {
+    /// Deep copy of the profile. See `try_clone()`.
+    ///
+    /// Panics if the profile can't be copied (e.g. out of memory).
+    #[track_caller]
+    fn clone(&self) -> Self {
+        self.try_clone().expect("profile copy")
+    }
+}
+
 impl<Context> Drop for Profile<Context> {
     fn drop(&mut self) {
         unsafe {
@@ -598,10 +1006,10 @@ fn tags_write() {
     let mut p = Profile::new_placeholder();
     let mut mlu = MLU::new(1);
     mlu.set_text_ascii("Testing", Locale::new("en_GB"));
-    assert!(p.write_tag(TagSignature::CopyrightTag, Tag::MLU(&mlu)));
+    p.write_tag(TagSignature::CopyrightTag, Tag::MLU(&mlu)).unwrap();
 
     let xyz = CIEXYZ{X:1., Y:2., Z:3.};
-    assert!(p.write_tag(TagSignature::RedColorantTag, Tag::CIEXYZ(&xyz)));
+    p.write_tag(TagSignature::RedColorantTag, Tag::CIEXYZ(&xyz)).unwrap();
 
     assert!(p.has_tag(TagSignature::CopyrightTag));
     assert!(p.has_tag(TagSignature::RedColorantTag));
@@ -618,6 +1026,67 @@ fn tags_write() {
     });
 }
 
+#[test]
+fn tags_write_types() {
+    let mut p = Profile::new_srgb();
+    let xyz = CIEXYZ{X:1., Y:2., Z:3.};
+    assert_eq!(Err(Error::TagTypeMismatch { sig: TagSignature::CopyrightTag, expected: "MLU" }),
+        p.write_tag(TagSignature::CopyrightTag, Tag::CIEXYZ(&xyz)));
+    assert_eq!(Err(Error::TagTypeMismatch { sig: TagSignature::DeviceSettingsTag, expected: "raw data" }),
+        p.write_tag(TagSignature::DeviceSettingsTag, Tag::CIEXYZ(&xyz)));
+    assert_eq!(Err(Error::TagTypeMismatch { sig: TagSignature::DataTag, expected: "raw data" }),
+        p.write_tag(TagSignature::DataTag, Tag::CIEXYZ(&xyz)));
+
+    let mut crd_info = MLU::new(1);
+    crd_info.set_text_ascii("Printer", Locale::none());
+    p.write_tag(TagSignature::CrdInfoTag, Tag::MLU(&crd_info)).unwrap();
+    let date = DateTime { year: 2023, month: 11, day: 5, hour: 8, minute: 30, second: 0 };
+    let order = ColorantOrder::new(&[2, 0, 1]).unwrap();
+    let cicp = ffi::VideoSignalType { ColourPrimaries: 9, TransferCharacteristics: 16, MatrixCoefficients: 0, VideoFullRangeFlag: 1 };
+    p.write_tag(TagSignature::CalibrationDateTimeTag, Tag::DateTime(date)).unwrap();
+    p.write_tag(TagSignature::ColorantOrderTag, Tag::ColorantOrder(&order)).unwrap();
+    p.write_tag(TagSignature::CicpTag, Tag::VideoSignalType(&cicp)).unwrap();
+    let arts = [[0.8951, 0.2664, -0.1614], [-0.7502, 1.7135, 0.0367], [0.0389, -0.0685, 1.0296]];
+    p.write_tag(TagSignature::ArgyllArtsTag, Tag::Matrix(&arts)).unwrap();
+
+    let p = Profile::new_icc(&p.icc().unwrap()).unwrap();
+    assert!(matches!(p.read_tag(TagSignature::CalibrationDateTimeTag), Tag::DateTime(d) if d == date));
+    assert!(matches!(p.read_tag(TagSignature::ColorantOrderTag), Tag::ColorantOrder(o) if *o == order));
+    match p.read_tag(TagSignature::CicpTag) {
+        Tag::VideoSignalType(v) => assert_eq!((9, 16, 0, 1), (v.ColourPrimaries, v.TransferCharacteristics, v.MatrixCoefficients, v.VideoFullRangeFlag)),
+        other => panic!("{other:?}"),
+    }
+    match p.read_tag(TagSignature::ArgyllArtsTag) {
+        Tag::Matrix(m) => assert!(m.iter().flatten().zip(arts.iter().flatten()).all(|(a, b)| (a - b).abs() < 0.0001)),
+        other => panic!("{other:?}"),
+    }
+    match p.read_tag(TagSignature::CrdInfoTag) {
+        Tag::MLU(mlu) => assert_eq!(Ok("Printer".to_owned()), mlu.text(Locale::none())),
+        other => panic!("{other:?}"),
+    }
+}
+
+#[test]
+fn colorants() {
+    let mut p = Profile::new_placeholder();
+    p.set_color_space(ColorSpaceSignature::MCH7Data);
+    p.set_pcs(ColorSpaceSignature::LabData);
+    let names = ["Cyan", "Magenta", "Yellow", "Black", "Orange", "Green", "Violet"];
+    let inks: Vec<_> = names.iter().zip(0..).map(|(&name, i)| Colorant { name: name.into(), pcs: [i, 2 * i, 3 * i] }).collect();
+    p.set_tag(TagSignature::ColorantTableTag, TagValue::ColorantTable(ColorantTable::new(&inks).unwrap())).unwrap();
+    p.write_tag(TagSignature::ColorantOrderTag, Tag::ColorantOrder(&ColorantOrder::new(&[3, 0, 1, 2, 4, 5, 6]).unwrap())).unwrap();
+
+    let p = Profile::new_icc(&p.icc().unwrap()).unwrap();
+    match p.read_tag(TagSignature::ColorantTableTag) {
+        Tag::ColorantTable(table) => assert_eq!(inks, table.colorants()),
+        other => panic!("{other:?}"),
+    }
+    match p.read_tag_owned(TagSignature::ColorantOrderTag) {
+        Some(TagValue::ColorantOrder(order)) => assert_eq!(vec![3, 0, 1, 2, 4, 5, 6], order.to_vec()),
+        other => panic!("{other:?}"),
+    }
+}
+
 impl fmt::Debug for Profile {
     #[cold]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -646,6 +1115,302 @@ fn icc() {
     assert!(format!("{prof:?}").contains("XYZ identity"));
 }
 
+#[test]
+fn from_reader() {
+    let icc = Profile::new_xyz().icc().unwrap();
+    let mut data = vec![0u8; 10];
+    data.extend_from_slice(&icc);
+    let mut reader = io::Cursor::new(data);
+    reader.set_position(10);
+    // the stream isn't kept, so it can be borrowed
+    let prof = Profile::from_reader(&mut reader).unwrap();
+    drop(reader);
+    assert!(format!("{prof:?}").contains("XYZ identity"));
+    assert_eq!(Profile::new_icc(&icc).unwrap().icc().unwrap(), prof.icc().unwrap());
+
+    let err = Profile::from_reader(io::Cursor::new(vec![1, 2, 3])).unwrap_err();
+    assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+
+    /// Fails to read tag data after the header and tag directory
+    struct Broken(io::Cursor<Vec<u8>>, u64);
+    impl Read for Broken {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0.position() >= self.1 {
+                return Err(io::ErrorKind::ConnectionReset.into());
+            }
+            self.0.read(buf)
+        }
+    }
+    impl Seek for Broken {
+        fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+            self.0.seek(pos)
+        }
+    }
+    let icc = Profile::new_srgb().icc().unwrap();
+    let tag_count = u32::from_be_bytes([icc[128], icc[129], icc[130], icc[131]]);
+    let directory_end = 132 + 12 * u64::from(tag_count);
+    let err = Profile::from_reader(Broken(io::Cursor::new(icc), directory_end)).unwrap_err();
+    assert_eq!(io::ErrorKind::ConnectionReset, err.kind());
+}
+
+#[test]
+fn write_to() {
+    let prof = Profile::new_srgb();
+    let icc = prof.icc().unwrap();
+    let mut out = io::Cursor::new(vec![7u8; 3]);
+    out.set_position(3);
+    assert_eq!(icc.len(), prof.write_to(&mut out).unwrap());
+    let out = out.into_inner();
+    assert_eq!(&[7, 7, 7], &out[..3]);
+    assert_eq!(icc[..], out[3..]);
+}
+
+#[test]
+fn header() {
+    let mut prof = Profile::new_srgb();
+    let mut header = prof.header();
+    assert_eq!(ProfileClassSignature::DisplayClass, header.device_class);
+    assert_eq!(ColorSpaceSignature::RgbData, header.color_space);
+    assert_eq!(ColorSpaceSignature::XYZData, header.pcs);
+    assert!(header.created.unwrap().year >= 2020);
+
+    header.flags = HeaderFlags::EMBEDDED | HeaderFlags::USE_WITH_EMBEDDED_DATA_ONLY;
+    header.attributes = HeaderAttributes::MATTE | HeaderAttributes::NEGATIVE;
+    header.model = 1234;
+    header.rendering_intent = Intent::Saturation;
+    prof.set_header(&header);
+
+    let copy = Profile::new_icc(&prof.icc().unwrap()).unwrap().header();
+    assert!(copy.flags.contains(HeaderFlags::EMBEDDED));
+    assert!(copy.attributes.contains(HeaderAttributes::MATTE));
+    assert!(!copy.attributes.contains(HeaderAttributes::TRANSPARENCY));
+    assert_eq!(1234, copy.model);
+    assert_eq!(Intent::Saturation, copy.rendering_intent);
+    assert_eq!(header.created, copy.created);
+}
+
+#[test]
+fn profile_id() {
+    let mut prof = Profile::new_srgb();
+    assert_eq!(Err(Error::MissingData), prof.verify_profile_id());
+    let id = prof.compute_profile_id().unwrap();
+    assert_ne!([0; 16], id);
+    assert_eq!([0; 16], prof.header().profile_id);
+
+    prof.set_default_profile_id();
+    assert_eq!(id, prof.header().profile_id);
+    assert_eq!(Ok(true), prof.verify_profile_id());
+
+    let mut prof = Profile::new_icc(&prof.icc().unwrap()).unwrap();
+    assert_eq!(Ok(true), prof.verify_profile_id());
+    // the rendering intent and flags are excluded from the ID, but the device class is not
+    prof.set_header_rendering_intent(Intent::AbsoluteColorimetric);
+    assert_eq!(Ok(true), prof.verify_profile_id());
+    prof.set_device_class(ProfileClassSignature::InputClass);
+    assert_eq!(Ok(false), prof.verify_profile_id());
+}
+
+#[test]
+fn profile_id_after_reading_tags() {
+    let mut prof = Profile::new_file("tests/sGray.icc").unwrap();
+    prof.set_default_profile_id();
+    let prof = Profile::new_icc(&prof.icc().unwrap()).unwrap();
+    let id = prof.compute_profile_id().unwrap();
+
+    // parsed tags are serialized differently than they were loaded
+    for sig in prof.tag_signatures() {
+        let _ = prof.read_tag(sig);
+    }
+    assert!(!prof.description().is_empty());
+    assert_eq!(Ok(id), prof.compute_profile_id());
+    assert_eq!(Ok(true), prof.verify_profile_id());
+    assert_eq!(Ok(true), prof.try_clone().unwrap().verify_profile_id());
+}
+
+#[test]
+fn convert_to_version() {
+    let mut prof = Profile::new_srgb();
+    assert!(prof.icc_version() >= IccVersion::new(4, 0, 0));
+    prof.convert_to_version(IccVersion::V2_1).unwrap();
+    assert_eq!(IccVersion::V2_1, prof.icc_version());
+
+    let icc = prof.icc().unwrap();
+    let prof = Profile::new_icc(&icc).unwrap();
+    assert_eq!(IccVersion::V2_1, prof.header().version);
+    assert!(prof.info(InfoType::Description, Locale::none()).unwrap().contains("sRGB"));
+    // v2 has no mluc and para types
+    assert!(!icc.windows(4).any(|w| w == b"mluc" || w == b"para"));
+    assert!(icc.windows(4).any(|w| w == b"desc"));
+    assert!(icc.windows(4).any(|w| w == b"curv"));
+
+    // the curves of sRGB are stored once and linked
+    let mut prof = Profile::new_icc(&Profile::new_srgb().icc().unwrap()).unwrap();
+    let sigs = prof.tag_signatures();
+    let linked = prof.tag_linked_to(TagSignature::BlueTRCTag, &sigs);
+    assert!(linked.is_some());
+    prof.convert_to_version(IccVersion::V2_1).unwrap();
+    assert_eq!(linked, prof.tag_linked_to(TagSignature::BlueTRCTag, &sigs));
+}
+
+#[test]
+fn clone() {
+    let mut base = Profile::new_srgb();
+    let mut copy = base.try_clone().unwrap();
+    assert_eq!(base.tag_signatures(), copy.tag_signatures());
+    assert_eq!(base.header().version, copy.header().version);
+    assert_eq!(base.info(InfoType::Description, Locale::none()), copy.info(InfoType::Description, Locale::none()));
+    let sigs = copy.tag_signatures();
+    assert_eq!(Some(TagSignature::RedTRCTag), copy.tag_linked_to(TagSignature::GreenTRCTag, &sigs));
+    assert_eq!(None, copy.tag_linked_to(TagSignature::RedTRCTag, &sigs));
+
+    // the copy is independent
+    assert!(copy.remove_tag(TagSignature::CopyrightTag));
+    assert!(base.has_tag(TagSignature::CopyrightTag));
+    base.set_device_class(ProfileClassSignature::InputClass);
+    assert_eq!(ProfileClassSignature::DisplayClass, copy.device_class());
+
+    let copy = base.clone();
+    assert_eq!(base.tag_signatures(), copy.tag_signatures());
+    assert_eq!(ProfileClassSignature::InputClass, copy.device_class());
+
+    let c = ThreadContext::new();
+    let p = Profile::new_srgb_context(&c);
+    assert_eq!(ColorSpaceSignature::RgbData, p.try_clone().unwrap().color_space());
+}
+
+#[test]
+fn raw_tags() {
+    let mut prof = Profile::new_srgb();
+    let desc = prof.read_raw_tag(TagSignature::ProfileDescriptionTag).unwrap();
+    assert_eq!(b"mluc", &desc[..4]);
+    assert!(prof.read_raw_tag(TagSignature::DeviceSettingsTag).is_none());
+
+    // not a type that LCMS knows
+    let private = b"priv\0\0\0\0hello";
+    prof.write_raw_tag(TagSignature::DeviceSettingsTag, private).unwrap();
+    assert!(matches!(prof.read_tag(TagSignature::DeviceSettingsTag), Tag::None));
+    assert_eq!(&private[..], &prof.read_raw_tag(TagSignature::DeviceSettingsTag).unwrap()[..]);
+    let copy = prof.try_clone().unwrap();
+    assert_eq!(&private[..], &copy.read_raw_tag(TagSignature::DeviceSettingsTag).unwrap()[..]);
+
+    let mut prof = Profile::new_icc(&copy.icc().unwrap()).unwrap();
+    assert_eq!(&private[..], &prof.read_raw_tag(TagSignature::DeviceSettingsTag).unwrap()[..]);
+    assert_eq!(desc, prof.read_raw_tag(TagSignature::ProfileDescriptionTag).unwrap());
+
+    prof.write_raw_tag(TagSignature::CopyrightTag, &desc).unwrap();
+    assert!(matches!(prof.read_tag(TagSignature::CopyrightTag), Tag::MLU(_)));
+    assert_eq!(desc, prof.read_raw_tag(TagSignature::CopyrightTag).unwrap());
+}
+
+#[test]
+fn tags() {
+    let prof = Profile::new_icc(&Profile::new_srgb().icc().unwrap()).unwrap();
+    let tags: Vec<_> = prof.tags().collect();
+    assert_eq!(prof.tag_signatures().len(), tags.len());
+
+    let desc = tags.iter().find(|t| t.signature == TagSignature::ProfileDescriptionTag).unwrap();
+    assert_eq!(u32::from_be_bytes(*b"mluc"), desc.type_signature);
+    assert_eq!(prof.read_raw_tag(TagSignature::ProfileDescriptionTag).unwrap().len(), desc.size);
+    assert_eq!(None, desc.linked_to);
+
+    let red = tags.iter().find(|t| t.signature == TagSignature::RedTRCTag).unwrap();
+    let green = tags.iter().find(|t| t.signature == TagSignature::GreenTRCTag).unwrap();
+    assert_eq!(Some(TagSignature::RedTRCTag), green.linked_to);
+    assert_eq!(red.type_signature, green.type_signature);
+    assert_eq!(red.size, green.size);
+}
+
+#[test]
+fn localized_info() {
+    let mut prof = Profile::new_srgb();
+    let en = Locale::new("en_US");
+    assert_eq!("sRGB built-in", prof.description()[&en]);
+    assert_eq!(prof.info(InfoType::Copyright, en).unwrap(), prof.copyright()[&en]);
+    assert!(prof.manufacturer().is_empty());
+
+    let mut text = MLU::new(2);
+    assert!(text.set_text("Colour", Locale::new("en_GB")));
+    assert!(text.set_text("Farbe", Locale::new("de_DE")));
+    prof.set_icc_version(IccVersion::V4_3);
+    prof.set_description(&text).unwrap();
+    prof.set_model(&text).unwrap();
+    let prof = Profile::new_icc(&prof.icc().unwrap()).unwrap();
+    assert_eq!(text.texts(), prof.description());
+    assert_eq!(text.texts(), prof.model());
+
+    let mut v2 = Profile::new_srgb();
+    v2.set_icc_version(IccVersion::V2_1);
+    v2.set_description(&text).unwrap();
+    let v2 = Profile::new_icc(&v2.icc().unwrap()).unwrap();
+    // `desc` has ASCII and Unicode copies of the same text
+    assert!(v2.description().values().all(|t| t == "Colour"));
+}
+
+#[test]
+fn meta_tag() {
+    let mut prof = Profile::new_srgb();
+    let mut meta = Dict::new().unwrap();
+    meta.add("build", Some("1.2.3"), None, None).unwrap();
+    prof.write_tag(TagSignature::MetaTag, Tag::Dict(&meta)).unwrap();
+
+    let prof = Profile::new_icc(&prof.icc().unwrap()).unwrap();
+    match prof.read_tag(TagSignature::MetaTag) {
+        Tag::Dict(d) => assert_eq!(Some("1.2.3".to_owned()), d.get("build")),
+        other => panic!("{other:?}"),
+    }
+    assert!(matches!(prof.read_tag_owned(TagSignature::MetaTag), Some(TagValue::Dict(_))));
+}
+
+#[test]
+fn owned_tags() {
+    let mut prof = Profile::new_srgb();
+    let white = CIEXYZ { X: 0.9642, Y: 1., Z: 0.8249 };
+    prof.set_tag(TagSignature::MediaWhitePointTag, TagValue::CIEXYZ(white)).unwrap();
+    assert_eq!(Err(Error::TagTypeMismatch { sig: TagSignature::RedTRCTag, expected: "ToneCurve" }),
+        prof.set_tag(TagSignature::RedTRCTag, TagValue::CIEXYZ(white)));
+    assert_eq!(Err(Error::TagTypeMismatch { sig: TagSignature::DeviceSettingsTag, expected: "raw data" }),
+        prof.set_tag(TagSignature::DeviceSettingsTag, TagValue::CIEXYZ(white)));
+
+    // Bradford D65 to D50
+    match prof.read_tag(TagSignature::ChromaticAdaptationTag) {
+        Tag::Matrix(m) => assert!((m[2][2] - 0.7519).abs() < 0.001, "{m:?}"),
+        other => panic!("{other:?}"),
+    }
+    let identity = [[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+    prof.set_tag(TagSignature::ChromaticAdaptationTag, TagValue::Matrix(identity)).unwrap();
+
+    let curves = [ToneCurve::new(1.8), ToneCurve::new(2.2), ToneCurve::new(2.4)];
+    prof.set_tag(TagSignature::VcgtTag, TagValue::VcgtCurves(curves.clone())).unwrap();
+    let mut screening = ffi::Screening { nChannels: 1, ..Default::default() };
+    screening.Channels[0] = ffi::ScreeningChannel { Frequency: 150., ScreenAngle: 45., SpotShape: ffi::SpotShape::ROUND };
+    prof.set_tag(TagSignature::ScreeningTag, TagValue::Screening(Box::new(screening))).unwrap();
+    let copy = prof.read_tag_owned(TagSignature::RedTRCTag).unwrap();
+
+    let prof = Profile::new_icc(&prof.icc().unwrap()).unwrap();
+    match prof.read_tag_owned(TagSignature::MediaWhitePointTag) {
+        Some(TagValue::CIEXYZ(xyz)) => assert!((xyz.Z - white.Z).abs() < 0.001),
+        other => panic!("{other:?}"),
+    }
+    assert!(matches!(prof.read_tag_owned(TagSignature::ChromaticAdaptationTag), Some(TagValue::Matrix(m)) if m == identity));
+    match prof.read_tag_owned(TagSignature::VcgtTag) {
+        Some(TagValue::VcgtCurves(vcgt)) => {
+            for (read, curve) in vcgt.iter().zip(&curves) {
+                assert!((read.estimated_gamma(0.1).unwrap() - curve.estimated_gamma(0.1).unwrap()).abs() < 0.01);
+            }
+        },
+        other => panic!("{other:?}"),
+    }
+    match prof.read_tag_owned(TagSignature::ScreeningTag) {
+        Some(TagValue::Screening(s)) => assert_eq!((1, 45.), (s.nChannels, s.Channels[0].ScreenAngle)),
+        other => panic!("{other:?}"),
+    }
+    drop(prof);
+
+    let mut other = Profile::new_placeholder();
+    other.set_tag(TagSignature::GrayTRCTag, copy).unwrap();
+    assert!(matches!(other.read_tag(TagSignature::GrayTRCTag), Tag::ToneCurve(_)));
+}
+
 #[test]
 fn bad_icc() {
     let err = Profile::new_icc(&[1, 2, 3]);
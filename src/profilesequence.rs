@@ -0,0 +1,197 @@
+use crate::context::Context;
+use crate::*;
+use foreign_types::{ForeignType, ForeignTypeRef};
+use std::fmt;
+use std::ptr;
+
+foreign_type! {
+    /// Descriptions of the profiles a device link was made from, as stored in `ProfileSequenceDescTag` and `ProfileSequenceIdTag`.
+    ///
+    /// Owned version of `ProfileSequenceRef`
+    pub unsafe type ProfileSequence {
+        type CType = ffi::SEQ;
+        fn drop = ffi::cmsFreeProfileSequenceDescription;
+        fn clone = ffi::cmsDupProfileSequenceDescription;
+    }
+}
+
+/// Description of one profile in a `ProfileSequence`
+#[derive(Debug, Clone)]
+pub struct ProfileSequenceEntry<'a> {
+    /// Manufacturer signature from the profile header
+    pub device_manufacturer: u32,
+    /// Model signature from the profile header
+    pub device_model: u32,
+    pub attributes: HeaderAttributes,
+    /// Signature of the `TechnologyTag`, or 0 if the profile didn't have one
+    pub technology: u32,
+    /// MD5 of the profile, or all zeros if not known
+    pub profile_id: [u8; 16],
+    /// Copy of the `DeviceMfgDescTag`
+    pub manufacturer: Option<&'a MLURef>,
+    /// Copy of the `DeviceModelDescTag`
+    pub model: Option<&'a MLURef>,
+    /// Copy of the `ProfileDescriptionTag`
+    pub description: Option<&'a MLURef>,
+}
+
+impl ProfileSequence {
+    /// Describes the profiles, in order of the transform they're used in. LCMS allows at most 255 profiles.
+    ///
+    /// The sequence is allocated in the context of the profiles.
+    pub fn from_profiles<Ctx: Context>(profiles: &[&Profile<Ctx>]) -> LCMSResult<Self> {
+        let len = u32::try_from(profiles.len()).map_err(|_| Error::ObjectCreationError)?;
+        let context = profiles.first().map_or(ptr::null_mut(), |p| unsafe { ffi::cmsGetProfileContextID(p.handle) });
+        let seq: Self = unsafe { Error::if_null(ffi::cmsAllocProfileSequenceDescription(context, len))? };
+        for (i, profile) in profiles.iter().enumerate() {
+            unsafe {
+                let desc = (*seq.as_ptr()).seq.add(i);
+                (*desc).deviceMfg = profile.header_manufacturer();
+                (*desc).deviceModel = profile.header_model();
+                (*desc).attributes = profile.header_attributes();
+                // the field is an enum, but profiles may have any value there, so it's copied as a number
+                let technology = ffi::cmsReadTag(profile.handle, TagSignature::TechnologyTag);
+                let technology = if technology.is_null() { 0 } else { technology.cast::<u32>().read_unaligned() };
+                ptr::addr_of_mut!((*desc).technology).cast::<u32>().write(technology);
+                ffi::cmsGetHeaderProfileID(profile.handle, ptr::addr_of_mut!((*desc).ProfileID).cast());
+                (*desc).Manufacturer = mlu_dup(profile, TagSignature::DeviceMfgDescTag);
+                (*desc).Model = mlu_dup(profile, TagSignature::DeviceModelDescTag);
+                (*desc).Description = mlu_dup(profile, TagSignature::ProfileDescriptionTag);
+            }
+        }
+        Ok(seq)
+    }
+}
+
+fn mlu_dup<Ctx: Context>(profile: &Profile<Ctx>, sig: TagSignature) -> *mut ffi::MLU {
+    match profile.read_tag(sig) {
+        Tag::MLU(mlu) => unsafe { ffi::cmsMLUdup(mlu.as_ptr()) },
+        _ => ptr::null_mut(),
+    }
+}
+
+impl ProfileSequenceRef {
+    /// Number of profiles in the sequence
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        unsafe { (*self.as_ptr()).n as usize }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Description of the profile at this position in the sequence
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<ProfileSequenceEntry<'_>> {
+        if index >= self.len() {
+            return None;
+        }
+        unsafe {
+            let desc = (*self.as_ptr()).seq.add(index);
+            Some(ProfileSequenceEntry {
+                device_manufacturer: (*desc).deviceMfg,
+                device_model: (*desc).deviceModel,
+                attributes: HeaderAttributes((*desc).attributes),
+                technology: ptr::addr_of!((*desc).technology).cast::<u32>().read(),
+                profile_id: std::mem::transmute::<ffi::ProfileID, [u8; 16]>((*desc).ProfileID),
+                manufacturer: mlu_ref((*desc).Manufacturer),
+                model: mlu_ref((*desc).Model),
+                description: mlu_ref((*desc).Description),
+            })
+        }
+    }
+
+    /// Iterate over descriptions of all profiles in the sequence
+    #[inline]
+    #[must_use]
+    pub fn iter(&self) -> ProfileSequenceIter<'_> {
+        ProfileSequenceIter { seq: self, index: 0 }
+    }
+}
+
+unsafe fn mlu_ref<'a>(mlu: *mut ffi::MLU) -> Option<&'a MLURef> {
+    if mlu.is_null() { None } else { Some(MLURef::from_ptr(mlu)) }
+}
+
+/// Iterator over entries of `ProfileSequenceRef`
+pub struct ProfileSequenceIter<'a> {
+    seq: &'a ProfileSequenceRef,
+    index: usize,
+}
+
+impl<'a> Iterator for ProfileSequenceIter<'a> {
+    type Item = ProfileSequenceEntry<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.seq.get(self.index)?;
+        self.index += 1;
+        Some(entry)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.seq.len().saturating_sub(self.index);
+        (len, Some(len))
+    }
+}
+
+impl<'a> IntoIterator for &'a ProfileSequenceRef {
+    type Item = ProfileSequenceEntry<'a>;
+    type IntoIter = ProfileSequenceIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl fmt::Debug for ProfileSequenceRef {
+    #[cold]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[test]
+fn profile_sequence() {
+    let srgb = Profile::new_srgb();
+    let mut lab = Profile::new_lab4_context(GlobalContext::new(), &white_point_from_temp(5000.).unwrap()).unwrap();
+    let mut header = lab.header();
+    header.manufacturer = u32::from_be_bytes(*b"TEST");
+    header.attributes = HeaderAttributes::MATTE;
+    lab.set_header(&header);
+    let seq = ProfileSequence::from_profiles(&[&srgb, &lab]).unwrap();
+    assert_eq!(2, seq.len());
+    assert!(ProfileSequence::from_profiles::<GlobalContext>(&[]).is_err());
+
+    let first = seq.get(0).unwrap();
+    assert_eq!(Ok("sRGB built-in".to_owned()), first.description.unwrap().text(Locale::new("en_US")));
+    assert!(first.manufacturer.is_none());
+    assert_eq!(0, first.technology);
+    let second = seq.get(1).unwrap();
+    assert_eq!(u32::from_be_bytes(*b"TEST"), second.device_manufacturer);
+    assert!(second.attributes.contains(HeaderAttributes::MATTE));
+    assert!(seq.get(2).is_none());
+
+    // psqd has device descriptions, and psid has profile descriptions
+    let mut link = Profile::new_placeholder();
+    link.set_icc_version(IccVersion::V4_3);
+    link.write_tag(TagSignature::ProfileSequenceDescTag, Tag::SEQ(&seq)).unwrap();
+    link.set_tag(TagSignature::ProfileSequenceIdTag, TagValue::SEQ(seq)).unwrap();
+    let link = Profile::new_icc(&link.icc().unwrap()).unwrap();
+    match link.read_tag(TagSignature::ProfileSequenceDescTag) {
+        Tag::SEQ(read) => {
+            assert_eq!(2, read.iter().count());
+            assert_eq!(u32::from_be_bytes(*b"TEST"), read.get(1).unwrap().device_manufacturer);
+        },
+        other => panic!("{other:?}"),
+    }
+    match link.read_tag_owned(TagSignature::ProfileSequenceIdTag) {
+        Some(TagValue::SEQ(read)) => {
+            assert_eq!(Ok("sRGB built-in".to_owned()), read.get(0).unwrap().description.unwrap().text(Locale::new("en_US")));
+        },
+        other => panic!("{other:?}"),
+    }
+}
@@ -1,5 +1,7 @@
+use crate::header::Tm;
 use crate::*;
-use foreign_types::ForeignTypeRef;
+use foreign_types::{ForeignType, ForeignTypeRef};
+use std::fmt;
 
 unsafe fn cast<T>(ptr: *const u8) -> &'static T {
     assert!(0 == ptr.align_offset(std::mem::align_of::<T>()), "Tag data pointer must be aligned");
@@ -12,6 +14,97 @@ unsafe fn aligned_mut<T>(ptr: *const u8) -> *mut T {
     ptr as *mut T
 }
 
+/// Copies LCMS-owned data. Unlike `to_owned()`, it checks for NULL, which LCMS returns when it runs out of memory.
+unsafe fn dup<T: ForeignType>(data: &T::Ref, dup: unsafe extern "C" fn(*const T::CType) -> *mut T::CType) -> Option<T>
+    where T::Ref: ForeignTypeRef<CType = T::CType> {
+    Error::if_null(dup(data.as_ptr())).ok()
+}
+
+/// Where and how a tag is stored in a profile, see `Profile::tags()`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TagInfo {
+    pub signature: TagSignature,
+    /// Four-character code of the tag's type, e.g. `u32::from_be_bytes(*b"mluc")`, or 0 if the tag is unreadable.
+    /// For linked tags it's the type of the tag they're linked to.
+    pub type_signature: u32,
+    /// Size of the tag data in bytes, including the type signature
+    pub size: usize,
+    /// The tag shares its data with this other tag
+    pub linked_to: Option<TagSignature>,
+}
+
+/// Owned value of a tag. Unlike `Tag`, it doesn't borrow from a profile, so it's easy to build from scratch.
+///
+/// See `Profile::read_tag_owned()` and `Profile::set_tag()`.
+#[derive(Clone)]
+#[non_exhaustive]
+pub enum TagValue {
+    CIExyYTRIPLE(CIExyYTRIPLE),
+    /// 3x3 matrix of the `ChromaticAdaptationTag` or `ArgyllArtsTag`, row by row
+    Matrix([[f64; 3]; 3]),
+    CIEXYZ(CIEXYZ),
+    ICCMeasurementConditions(ffi::ICCMeasurementConditions),
+    ICCViewingConditions(ffi::ICCViewingConditions),
+    /// Halftone screens of the `ScreeningTag`
+    Screening(Box<ffi::Screening>),
+    /// Unicode string
+    MLU(MLU),
+    /// A palette
+    NamedColorList(NamedColorList),
+    Pipeline(Pipeline),
+    Intent(Intent),
+    ColorimetricIntentImageState(ffi::ColorimetricIntentImageState),
+    Technology(ffi::TechnologySignature),
+    ToneCurve(ToneCurve),
+    VcgtCurves([ToneCurve; 3]),
+    ColorantOrder(ColorantOrder),
+    /// Names of colorants of the device or of the output
+    ColorantTable(ColorantTable),
+    DateTime(DateTime),
+    /// Coding-independent code points (`cicp`)
+    VideoSignalType(ffi::VideoSignalType),
+    /// Name/value pairs of the `MetaTag`
+    Dict(Dict),
+    /// Descriptions of profiles a device link was made from
+    SEQ(ProfileSequence),
+}
+
+impl TagValue {
+    /// Borrows the value as a `Tag`
+    #[must_use]
+    pub fn as_tag(&self) -> Tag<'_> {
+        match self {
+            TagValue::CIExyYTRIPLE(data) => Tag::CIExyYTRIPLE(data),
+            TagValue::Matrix(data) => Tag::Matrix(data),
+            TagValue::CIEXYZ(data) => Tag::CIEXYZ(data),
+            TagValue::ICCMeasurementConditions(data) => Tag::ICCMeasurementConditions(data),
+            TagValue::ICCViewingConditions(data) => Tag::ICCViewingConditions(data),
+            TagValue::Screening(data) => Tag::Screening(data),
+            TagValue::MLU(data) => Tag::MLU(data),
+            TagValue::NamedColorList(data) => Tag::NamedColorList(data),
+            TagValue::Pipeline(data) => Tag::Pipeline(data),
+            TagValue::Intent(data) => Tag::Intent(*data),
+            TagValue::ColorimetricIntentImageState(data) => Tag::ColorimetricIntentImageState(*data),
+            TagValue::Technology(data) => Tag::Technology(*data),
+            TagValue::ToneCurve(data) => Tag::ToneCurve(data),
+            TagValue::VcgtCurves([r, g, b]) => Tag::VcgtCurves([r, g, b]),
+            TagValue::ColorantOrder(data) => Tag::ColorantOrder(data),
+            TagValue::ColorantTable(data) => Tag::ColorantTable(data),
+            TagValue::DateTime(data) => Tag::DateTime(*data),
+            TagValue::VideoSignalType(data) => Tag::VideoSignalType(data),
+            TagValue::Dict(data) => Tag::Dict(data),
+            TagValue::SEQ(data) => Tag::SEQ(data),
+        }
+    }
+}
+
+impl fmt::Debug for TagValue {
+    #[cold]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_tag().fmt(f)
+    }
+}
+
 impl<'a> Tag<'a> {
     #[must_use] pub fn is_none(&self) -> bool {
         match *self {
@@ -20,93 +113,202 @@ impl<'a> Tag<'a> {
         }
     }
 
-    pub unsafe fn data_for_signature(&self, sig: TagSignature) -> *const u8 {
+    /// Name of the `Tag` variant that LCMS reads and writes for tags with this signature
+    pub(crate) fn variant_for_signature(sig: TagSignature) -> Option<&'static str> {
         use crate::TagSignature::*;
-        match (sig, self) {
-            (RedColorantTag, &Tag::CIEXYZ(data)) |
-            (BlueColorantTag, &Tag::CIEXYZ(data)) |
-            (GreenColorantTag, &Tag::CIEXYZ(data)) |
-            (LuminanceTag, &Tag::CIEXYZ(data)) |
-            (MediaBlackPointTag, &Tag::CIEXYZ(data)) |
-            (MediaWhitePointTag, &Tag::CIEXYZ(data)) => {
-                data as *const _ as *const u8
-            },
-            (ViewingCondDescTag, &Tag::MLU(data)) |
-            (CharTargetTag, &Tag::MLU(data)) |
-            (CopyrightTag, &Tag::MLU(data)) |
-            (DeviceMfgDescTag, &Tag::MLU(data)) |
-            (DeviceModelDescTag, &Tag::MLU(data)) |
-            (ProfileDescriptionTag, &Tag::MLU(data)) |
-            (ProfileDescriptionMLTag, &Tag::MLU(data)) |
-            (ScreeningDescTag, &Tag::MLU(data)) => {
-                data.as_ptr() as *const _
-            },
-            (ChromaticityTag, &Tag::CIExyYTRIPLE(data)) |
-            (ChromaticAdaptationTag, &Tag::CIExyYTRIPLE(data)) => {
-                data as *const _ as *const u8
-            },
-            (ColorantTableTag, &Tag::NamedColorList(data)) |
-            (ColorantTableOutTag, &Tag::NamedColorList(data)) |
-            (CrdInfoTag, &Tag::NamedColorList(data)) |
-            (NamedColor2Tag, &Tag::NamedColorList(data)) => data as *const _ as *const u8,
-            (DataTag, &Tag::ICCData(data)) |
-            (Ps2CRD0Tag, &Tag::ICCData(data)) |
-            (Ps2CRD1Tag, &Tag::ICCData(data)) |
-            (Ps2CRD2Tag, &Tag::ICCData(data)) |
-            (Ps2CRD3Tag, &Tag::ICCData(data)) |
-            (Ps2CSATag, &Tag::ICCData(data)) => data as *const _ as *const u8,
-            (Ps2RenderingIntentTag, &Tag::ICCData(data)) => data as *const _ as *const u8,
-            (AToB0Tag, &Tag::Pipeline(data)) |
-            (AToB1Tag, &Tag::Pipeline(data)) |
-            (AToB2Tag, &Tag::Pipeline(data)) |
-            (BToA0Tag, &Tag::Pipeline(data)) |
-            (BToA1Tag, &Tag::Pipeline(data)) |
-            (BToA2Tag, &Tag::Pipeline(data)) |
-            (DToB0Tag, &Tag::Pipeline(data)) |
-            (DToB1Tag, &Tag::Pipeline(data)) |
-            (DToB2Tag, &Tag::Pipeline(data)) |
-            (DToB3Tag, &Tag::Pipeline(data)) |
-            (BToD0Tag, &Tag::Pipeline(data)) |
-            (BToD1Tag, &Tag::Pipeline(data)) |
-            (BToD2Tag, &Tag::Pipeline(data)) |
-            (BToD3Tag, &Tag::Pipeline(data)) |
-            (GamutTag, &Tag::Pipeline(data)) |
-            (Preview0Tag, &Tag::Pipeline(data)) |
-            (Preview1Tag, &Tag::Pipeline(data)) |
-            (Preview2Tag, &Tag::Pipeline(data)) => {
-                data.as_ptr() as *const _
-            },
-            (BlueTRCTag, &Tag::ToneCurve(data)) |
-            (GrayTRCTag, &Tag::ToneCurve(data)) |
-            (GreenTRCTag, &Tag::ToneCurve(data)) |
-            (RedTRCTag, &Tag::ToneCurve(data)) => {
-                data.as_ptr() as *const _
-            },
-            (ColorimetricIntentImageStateTag, &Tag::ColorimetricIntentImageState(ref data)) => {
-                data as *const ffi::ColorimetricIntentImageState as *const u8
-            },
-            (PerceptualRenderingIntentGamutTag, &Tag::Intent(ref data)) |
-            (SaturationRenderingIntentGamutTag, &Tag::Intent(ref data)) => {
-                data as *const ffi::Intent as *const u8
-            },
-            (TechnologyTag, &Tag::Technology(ref data)) => data as *const _ as *const u8,
-            (MeasurementTag, &Tag::ICCMeasurementConditions(data)) => {
-                data as *const _ as *const u8
-            },
-            (ProfileSequenceDescTag, &Tag::SEQ(data)) |
-            (ProfileSequenceIdTag, &Tag::SEQ(data)) => {
-                data as *const _ as *const u8
-            },
-            (ScreeningTag, &Tag::Screening(data)) => data as *const _ as *const u8,
-            (UcrBgTag, &Tag::UcrBg(data)) => data as *const _ as *const u8,
-            (VcgtTag, &Tag::VcgtCurves(arr)) => arr[0].as_ptr() as *const _,
-            (ViewingConditionsTag, &Tag::ICCViewingConditions(data)) => {
-                data as *const _ as *const u8
+        Some(match sig {
+            BlueColorantTag |
+            GreenColorantTag |
+            LuminanceTag |
+            MediaBlackPointTag |
+            MediaWhitePointTag |
+            RedColorantTag => "CIEXYZ",
+            CharTargetTag |
+            CopyrightTag |
+            CrdInfoTag |
+            DeviceMfgDescTag |
+            DeviceModelDescTag |
+            ProfileDescriptionTag |
+            ProfileDescriptionMLTag |
+            ScreeningDescTag |
+            ViewingCondDescTag => "MLU",
+            ChromaticityTag => "CIExyYTRIPLE",
+            ChromaticAdaptationTag |
+            ArgyllArtsTag => "Matrix",
+            NamedColor2Tag => "NamedColorList",
+            ColorantTableTag |
+            ColorantTableOutTag => "ColorantTable",
+            Ps2CRD0Tag |
+            Ps2CRD1Tag |
+            Ps2CRD2Tag |
+            Ps2CRD3Tag |
+            Ps2CSATag |
+            Ps2RenderingIntentTag => "ICCData",
+            AToB0Tag |
+            AToB1Tag |
+            AToB2Tag |
+            BToA0Tag |
+            BToA1Tag |
+            BToA2Tag |
+            DToB0Tag |
+            DToB1Tag |
+            DToB2Tag |
+            DToB3Tag |
+            BToD0Tag |
+            BToD1Tag |
+            BToD2Tag |
+            BToD3Tag |
+            GamutTag |
+            Preview0Tag |
+            Preview1Tag |
+            Preview2Tag => "Pipeline",
+            BlueTRCTag |
+            GrayTRCTag |
+            GreenTRCTag |
+            RedTRCTag => "ToneCurve",
+            ColorimetricIntentImageStateTag => "ColorimetricIntentImageState",
+            PerceptualRenderingIntentGamutTag |
+            SaturationRenderingIntentGamutTag => "Intent",
+            TechnologyTag => "Technology",
+            MeasurementTag => "ICCMeasurementConditions",
+            ProfileSequenceDescTag |
+            ProfileSequenceIdTag => "SEQ",
+            ScreeningTag => "Screening",
+            UcrBgTag => "UcrBg",
+            VcgtTag => "VcgtCurves",
+            ViewingConditionsTag => "ICCViewingConditions",
+            ColorantOrderTag => "ColorantOrder",
+            CalibrationDateTimeTag |
+            DateTimeTag => "DateTime",
+            CicpTag => "VideoSignalType",
+            MHC2Tag => "MHC2",
+            MetaTag => "Dict",
+            // LCMS can't read or write DeviceSettingsTag and other deprecated tags, use raw tags for them
+            _ => return None,
+        })
+    }
+
+    fn variant(&self) -> &'static str {
+        match *self {
+            Tag::CIExyYTRIPLE(_) => "CIExyYTRIPLE",
+            Tag::Matrix(_) => "Matrix",
+            Tag::CIEXYZ(_) => "CIEXYZ",
+            Tag::ICCData(_) => "ICCData",
+            Tag::ICCMeasurementConditions(_) => "ICCMeasurementConditions",
+            Tag::ICCViewingConditions(_) => "ICCViewingConditions",
+            Tag::MLU(_) => "MLU",
+            Tag::NamedColorList(_) => "NamedColorList",
+            Tag::Pipeline(_) => "Pipeline",
+            Tag::Screening(_) => "Screening",
+            Tag::SEQ(_) => "SEQ",
+            Tag::Intent(_) => "Intent",
+            Tag::ColorimetricIntentImageState(_) => "ColorimetricIntentImageState",
+            Tag::Technology(_) => "Technology",
+            Tag::ToneCurve(_) => "ToneCurve",
+            Tag::UcrBg(_) => "UcrBg",
+            Tag::VcgtCurves(_) => "VcgtCurves",
+            Tag::ColorantOrder(_) => "ColorantOrder",
+            Tag::ColorantTable(_) => "ColorantTable",
+            Tag::DateTime(_) => "DateTime",
+            Tag::VideoSignalType(_) => "VideoSignalType",
+            Tag::MHC2(_) => "MHC2",
+            Tag::Dict(_) => "Dict",
+            Tag::None => "None",
+        }
+    }
+
+    fn check_signature(&self, sig: TagSignature) -> LCMSResult<()> {
+        let expected = Self::variant_for_signature(sig).unwrap_or("raw data");
+        if expected != self.variant() {
+            return Err(Error::TagTypeMismatch { sig, expected });
+        }
+        Ok(())
+    }
+
+    /// Pointer to the data in the form `cmsWriteTag` expects, or an error if tags with this signature need a different type.
+    ///
+    /// `Tag::DateTime` has no such pointer (LCMS needs a C `struct tm`), so it's `MissingData`. `write_to()` handles it.
+    pub(crate) fn data_for_signature(&self, sig: TagSignature) -> LCMSResult<*const u8> {
+        self.check_signature(sig)?;
+        Ok(match *self {
+            Tag::CIExyYTRIPLE(data) => data as *const _ as *const u8,
+            Tag::Matrix(data) => data as *const _ as *const u8,
+            Tag::CIEXYZ(data) => data as *const _ as *const u8,
+            Tag::ICCData(data) => data as *const _ as *const u8,
+            Tag::ICCMeasurementConditions(data) => data as *const _ as *const u8,
+            Tag::ICCViewingConditions(data) => data as *const _ as *const u8,
+            Tag::MLU(data) => data.as_ptr() as *const _,
+            Tag::NamedColorList(data) => data.as_ptr() as *const _,
+            Tag::Pipeline(data) => data.as_ptr() as *const _,
+            Tag::Screening(data) => data as *const _ as *const u8,
+            Tag::SEQ(data) => data.as_ptr() as *const _,
+            Tag::Intent(ref data) => data as *const ffi::Intent as *const u8,
+            Tag::ColorimetricIntentImageState(ref data) => data as *const ffi::ColorimetricIntentImageState as *const u8,
+            Tag::Technology(ref data) => data as *const ffi::TechnologySignature as *const u8,
+            Tag::ToneCurve(data) => data.as_ptr() as *const _,
+            Tag::UcrBg(data) => data as *const _ as *const u8,
+            // LCMS reads and writes vcgt as an array of 3 curve pointers, not as the first curve
+            Tag::VcgtCurves(ref arr) => arr.as_ptr() as *const u8,
+            Tag::ColorantOrder(data) => data.as_ptr(),
+            Tag::ColorantTable(data) => data.as_ptr() as *const _,
+            Tag::VideoSignalType(data) => data as *const _ as *const u8,
+            Tag::MHC2(data) => data as *const _ as *const u8,
+            Tag::Dict(data) => data.as_ptr() as *const _,
+            Tag::DateTime(_) => return Err(Error::MissingData),
+            Tag::None => std::ptr::null(),
+        })
+    }
+
+    pub(crate) unsafe fn write_to(&self, profile: ffi::HPROFILE, sig: TagSignature) -> LCMSResult<()> {
+        let tm;
+        let data = match *self {
+            Tag::DateTime(ref date) => {
+                self.check_signature(sig)?;
+                tm = Tm::new(date);
+                &tm as *const Tm as *const u8
             },
-            (sig, _) => panic!("Signature type {:?} does not support this tag data type", sig),
+            _ => self.data_for_signature(sig)?,
+        };
+        if ffi::cmsWriteTag(profile, sig, data.cast()) != 0 {
+            Ok(())
+        } else {
+            Err(Error::ObjectCreationError)
         }
     }
 
+    /// Copies the data, so that it doesn't borrow from the profile.
+    ///
+    /// Returns `None` for `Tag::None`, for `ICCData` (its bytes follow the struct, so it has no fixed size),
+    /// for `UcrBg` and `MHC2` (they point to curves and tables owned by the profile), and if LCMS fails to copy the data.
+    #[must_use]
+    pub fn to_value(&self) -> Option<TagValue> {
+        Some(match *self {
+            Tag::CIExyYTRIPLE(data) => TagValue::CIExyYTRIPLE(*data),
+            Tag::Matrix(data) => TagValue::Matrix(*data),
+            Tag::CIEXYZ(data) => TagValue::CIEXYZ(*data),
+            Tag::ICCMeasurementConditions(data) => TagValue::ICCMeasurementConditions(*data),
+            Tag::ICCViewingConditions(data) => TagValue::ICCViewingConditions(*data),
+            Tag::Screening(data) => TagValue::Screening(Box::new(*data)),
+            Tag::MLU(data) => TagValue::MLU(unsafe { dup(data, ffi::cmsMLUdup)? }),
+            Tag::NamedColorList(data) => TagValue::NamedColorList(unsafe { dup(data, ffi::cmsDupNamedColorList)? }),
+            Tag::Pipeline(data) => TagValue::Pipeline(unsafe { dup(data, ffi::cmsPipelineDup)? }),
+            Tag::Intent(data) => TagValue::Intent(data),
+            Tag::ColorimetricIntentImageState(data) => TagValue::ColorimetricIntentImageState(data),
+            Tag::Technology(data) => TagValue::Technology(data),
+            Tag::ToneCurve(data) => TagValue::ToneCurve(unsafe { dup(data, ffi::cmsDupToneCurve)? }),
+            Tag::VcgtCurves([r, g, b]) => unsafe {
+                TagValue::VcgtCurves([dup(r, ffi::cmsDupToneCurve)?, dup(g, ffi::cmsDupToneCurve)?, dup(b, ffi::cmsDupToneCurve)?])
+            },
+            Tag::ColorantOrder(data) => TagValue::ColorantOrder(*data),
+            Tag::ColorantTable(data) => TagValue::ColorantTable(unsafe { dup(data, ffi::cmsDupNamedColorList)? }),
+            Tag::DateTime(data) => TagValue::DateTime(data),
+            Tag::VideoSignalType(data) => TagValue::VideoSignalType(*data),
+            Tag::Dict(data) => TagValue::Dict(data.try_to_owned().ok()?),
+            Tag::SEQ(data) => TagValue::SEQ(unsafe { dup(data, ffi::cmsDupProfileSequenceDescription)? }),
+            Tag::ICCData(_) | Tag::UcrBg(_) | Tag::MHC2(_) | Tag::None => return None,
+        })
+    }
+
     pub unsafe fn new(sig: TagSignature, data: *const u8) -> Self {
         if data.is_null() {
             return Tag::None;
@@ -121,19 +323,19 @@ impl<'a> Tag<'a> {
             RedColorantTag => Tag::CIEXYZ(cast(data)),
             CharTargetTag |
             CopyrightTag |
+            CrdInfoTag |
             DeviceMfgDescTag |
             DeviceModelDescTag |
             ProfileDescriptionTag |
             ProfileDescriptionMLTag |
             ScreeningDescTag |
             ViewingCondDescTag => Tag::MLU(MLURef::from_ptr(aligned_mut(data))),
-            ChromaticityTag |
-            ChromaticAdaptationTag => Tag::CIExyYTRIPLE(cast(data)),
-            ColorantTableTag |
-            ColorantTableOutTag |
-            CrdInfoTag |
+            ChromaticityTag => Tag::CIExyYTRIPLE(cast(data)),
+            ChromaticAdaptationTag |
+            ArgyllArtsTag => Tag::Matrix(cast(data)),
             NamedColor2Tag => Tag::NamedColorList(NamedColorListRef::from_ptr(aligned_mut(data))),
-            DataTag |
+            ColorantTableTag |
+            ColorantTableOutTag => Tag::ColorantTable(ColorantTableRef::from_ptr(aligned_mut(data))),
             Ps2CRD0Tag |
             Ps2CRD1Tag |
             Ps2CRD2Tag |
@@ -172,15 +374,25 @@ impl<'a> Tag<'a> {
             TechnologyTag => Tag::Technology(data.cast::<ffi::TechnologySignature>().read_unaligned()),
             MeasurementTag => Tag::ICCMeasurementConditions(cast(data)),
             ProfileSequenceDescTag |
-            ProfileSequenceIdTag => Tag::SEQ(cast(data)),
+            ProfileSequenceIdTag => Tag::SEQ(ProfileSequenceRef::from_ptr(aligned_mut(data))),
             ScreeningTag => Tag::Screening(cast(data)),
             UcrBgTag => Tag::UcrBg(cast(data)),
+            // an array of 3 curve pointers
             VcgtTag => Tag::VcgtCurves([
-                ToneCurveRef::from_ptr(aligned_mut(data)),
+                ToneCurveRef::from_ptr(*aligned_mut::<*mut ffi::ToneCurve>(data)),
                 ToneCurveRef::from_ptr(*(aligned_mut::<*mut ffi::ToneCurve>(data).offset(1))),
                 ToneCurveRef::from_ptr(*(aligned_mut::<*mut ffi::ToneCurve>(data).offset(2))),
             ]),
             ViewingConditionsTag => Tag::ICCViewingConditions(cast(data)),
+            ColorantOrderTag => Tag::ColorantOrder(ColorantOrder::from_ptr(data)),
+            CalibrationDateTimeTag |
+            DateTimeTag => match Tm::from_ptr(data.cast()).date_time() {
+                Some(date) => Tag::DateTime(date),
+                None => Tag::None,
+            },
+            CicpTag => Tag::VideoSignalType(cast(data)),
+            MHC2Tag => Tag::MHC2(cast(data)),
+            MetaTag => Tag::Dict(DictRef::from_ptr(data as *mut _)),
             _ => Tag::None,
         }
     }
@@ -1,8 +1,13 @@
 use crate::context::Context;
 use crate::*;
+use foreign_types::ForeignTypeRef;
 use std::fmt;
 use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
 use std::os::raw::c_void;
+use std::ptr;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 /// Conversion between two ICC profiles.
 ///
@@ -25,7 +30,8 @@ use std::os::raw::c_void;
 /// ```
 ///
 ///  * `InputPixelFormat` — e.g. `(u8,u8,u8)` or struct `RGB<u8>`, etc.
-///     The type must have appropriate number of bytes per pixel (i.e. you can't just use `[u8]` for everything).
+///    The type must have appropriate number of bytes per pixel (i.e. you can't just use `[u8]` for everything).
+///    For planar formats it's the type of a single channel, e.g. `u8`, and `transform_planes()` is used instead.
 ///  * `OutputPixelFormat` — similar to `InputPixelFormat`. If both are the same, then `transform_in_place()` function works.
 ///  * `Context` — it's `GlobalContext` for the default non-thread-safe version, or `ThreadContext` for thread-safe version.
 ///  * `Flags` — `AllowCache` or `DisallowCache`. If you disallow cache, then the transform will be accessible from multiple threads.
@@ -37,6 +43,10 @@ use std::os::raw::c_void;
 ///
 pub struct Transform<InputPixelFormat, OutputPixelFormat, Context = GlobalContext, Flags = AllowCache> {
     pub(crate) handle: ffi::HTRANSFORM,
+    input_color_space: ColorSpaceSignature,
+    output_color_space: ColorSpaceSignature,
+    intent: Intent,
+    flags: u32,
     _from: PhantomData<InputPixelFormat>,
     _to: PhantomData<OutputPixelFormat>,
     _context_ref: PhantomData<Context>,
@@ -107,9 +117,135 @@ impl<InputPixelFormat: Copy + Clone, OutputPixelFormat: Copy + Clone> Transform<
     }
 }
 
+impl<InputPixel: Pixel, OutputPixel: Pixel> Transform<InputPixel, OutputPixel, GlobalContext, AllowCache> {
+    /// Creates a color transform for pixel types that implement `Pixel`, so that their `PixelFormat` doesn't have to be specified.
+    ///
+    /// Fails with `Error::ColorSpaceMismatch` if the pixel types don't match color spaces of the profiles,
+    /// e.g. when `Rgb` pixels are used with a CMYK profile.
+    ///
+    /// ```rust,ignore
+    /// let t = Transform::<Rgb<u8>, Cmyk<u16>>::new_typed(&srgb, &press_profile, Intent::Perceptual)?;
+    /// ```
+    #[inline]
+    pub fn new_typed(input: &Profile, output: &Profile, intent: Intent) -> LCMSResult<Self> {
+        Self::new_typed_flags_context(GlobalContext::new(), input, output, intent, Flags::default())
+    }
+}
+
+impl<InputPixel: Pixel, OutputPixel: Pixel, Ctx: Context, Fl: CacheFlag> Transform<InputPixel, OutputPixel, Ctx, Fl> {
+    /// Same as `new_typed()`, but allows specifying flags and a thread-safe context.
+    pub fn new_typed_flags_context(context: impl AsRef<Ctx>, input: &Profile<Ctx>, output: &Profile<Ctx>,
+                                   intent: Intent, flags: Flags<Fl>) -> LCMSResult<Self> {
+        Self::new_flags_context(context, input, InputPixel::FORMAT, output, OutputPixel::FORMAT, intent, flags)
+    }
+}
+
+impl<OutputPixelFormat: Copy + Clone> Transform<u16, OutputPixelFormat, GlobalContext, AllowCache> {
+    /// Creates a transform from colors of a named color profile (a palette of spot colors, `NamedColorClass`)
+    /// to device colorant values of that profile.
+    ///
+    /// Input pixels are `u16` indices of colors in the palette. Use `named_color_index()` to find colors by name.
+    #[inline]
+    pub fn new_named_color(named: &Profile, out_format: PixelFormat, intent: Intent) -> LCMSResult<Self> {
+        Self::new_named_color_context(GlobalContext::new(), named, out_format, intent, Flags::default())
+    }
+
+    /// Creates a transform from colors of a named color profile to the `output` profile.
+    /// Use a Lab or XYZ profile as the output to get PCS values of the colors.
+    ///
+    /// Input pixels are `u16` indices of colors in the palette. Use `named_color_index()` to find colors by name.
+    #[inline]
+    pub fn new_named_color_to(named: &Profile, output: &Profile, out_format: PixelFormat, intent: Intent) -> LCMSResult<Self> {
+        Self::new_named_color_to_context(GlobalContext::new(), named, output, out_format, intent, Flags::default())
+    }
+}
+
+impl<OutputPixelFormat: Copy + Clone, Ctx: Context, Fl: CacheFlag> Transform<u16, OutputPixelFormat, Ctx, Fl> {
+    /// See `new_named_color()`
+    pub fn new_named_color_context(context: impl AsRef<Ctx>, named: &Profile<Ctx>, out_format: PixelFormat, intent: Intent, flags: Flags<Fl>) -> LCMSResult<Self> {
+        let in_format = PixelFormat::NAMED_COLOR_INDEX;
+        let spaces = color_spaces(&[named]);
+        Self::check_formats(in_format, out_format, spaces, flags.bits())?;
+        Self::new_handle(unsafe {
+            ffi::cmsCreateTransformTHR(context.as_ref().as_ptr(),
+                named.handle, in_format,
+                ptr::null_mut(), out_format,
+                intent, flags.bits())
+        }, spaces, intent, flags.bits())
+    }
+
+    /// See `new_named_color_to()`
+    pub fn new_named_color_to_context(context: impl AsRef<Ctx>, named: &Profile<Ctx>, output: &Profile<Ctx>, out_format: PixelFormat, intent: Intent, flags: Flags<Fl>) -> LCMSResult<Self> {
+        Self::new_flags_context(context, named, PixelFormat::NAMED_COLOR_INDEX, output, out_format, intent, flags)
+    }
+
+    /// Finds index of a color in the palette of a named color transform.
+    /// The index can be used as an input pixel.
+    #[must_use]
+    pub fn named_color_index(&self, color_name: &str) -> Option<u16> {
+        let index = self.named_color_list()?.find(color_name)?;
+        u16::try_from(index).ok()
+    }
+}
+
+/// Color spaces of the input and output of a chain of profiles. Follows `GetXFormColorSpaces()` of LCMS,
+/// so a profile is used in the input direction unless the previous one ended in XYZ or Lab.
+pub(crate) fn color_spaces<Ctx: Context>(profiles: &[&Profile<Ctx>]) -> (ColorSpaceSignature, ColorSpaceSignature) {
+    let mut entry = ColorSpaceSignature::Sig1colorData;
+    let mut post = profiles.first().map_or(ColorSpaceSignature::Sig1colorData, |p| p.color_space());
+    for (i, profile) in profiles.iter().enumerate() {
+        let is_input = post != ColorSpaceSignature::XYZData && post != ColorSpaceSignature::LabData;
+        let class = profile.device_class();
+        let (color_in, color_out) = if class == ProfileClassSignature::NamedColorClass {
+            (ColorSpaceSignature::Sig1colorData, if profiles.len() > 1 { profile.pcs() } else { profile.color_space() })
+        } else if is_input || class == ProfileClassSignature::LinkClass {
+            (profile.color_space(), profile.pcs())
+        } else {
+            (profile.pcs(), profile.color_space())
+        };
+        if i == 0 {
+            entry = color_in;
+        }
+        post = color_out;
+    }
+    (entry, post)
+}
+
+/// For planar formats the pixel type is the type of a single sample (e.g. `u8` for `RGB_8_PLANAR`),
+/// since each channel is stored separately. Without a color space only the size of the pixel type is checked.
+fn check_format<Z>(format: PixelFormat, color_space: Option<ColorSpaceSignature>) -> LCMSResult<()> {
+    let expected = if format.planar() { format.bytes_per_channel() } else { format.bytes_per_pixel() };
+    let actual = std::mem::size_of::<Z>();
+    if expected != actual {
+        return Err(Error::PixelSizeMismatch { expected, actual });
+    }
+    if let Some(color_space) = color_space {
+        check_color_space(format, color_space)?;
+        let expected = color_space.channels() as usize;
+        let actual = format.channels();
+        if actual != 0 && actual != expected {
+            return Err(Error::ChannelCountMismatch { expected, actual });
+        }
+    }
+    Ok(())
+}
+
+/// `PT_ANY` formats are accepted for any color space
+fn check_color_space(format: PixelFormat, color_space: ColorSpaceSignature) -> LCMSResult<()> {
+    // Lab v2 encoding is still Lab
+    let normalize = |pt: u32| if pt == 30 { 10 } else { pt };
+    let format_space = normalize(pixel::format_color_space(format));
+    if format_space != 0 && format_space != normalize(color_space.pixel_format().0) {
+        return Err(Error::ColorSpaceMismatch(color_space));
+    }
+    Ok(())
+}
+
 impl<PixelFormat: Copy + Clone, Ctx: Context, C> Transform<PixelFormat, PixelFormat, Ctx, C> {
     #[inline]
+    #[track_caller]
     pub fn transform_in_place(&self, srcdst: &mut [PixelFormat]) {
+        assert!(!self.input_format().planar() && !self.output_format().planar(), "Use transform_planes() for planar formats");
         let size = srcdst.len();
         assert!(size < std::u32::MAX as usize);
         unsafe {
@@ -134,35 +270,40 @@ impl<InputPixelFormat: Copy + Clone, OutputPixelFormat: Copy + Clone, Ctx: Conte
 
 impl<InputPixelFormat: Copy + Clone, OutputPixelFormat: Copy + Clone, Ctx: Context, Fl: CacheFlag> Transform<InputPixelFormat, OutputPixelFormat, Ctx, Fl> {
     #[inline]
-    fn new_handle(handle: ffi::HTRANSFORM, in_format: PixelFormat, out_format: PixelFormat) -> LCMSResult<Self> {
+    pub(crate) fn new_handle(handle: ffi::HTRANSFORM, (input_color_space, output_color_space): (ColorSpaceSignature, ColorSpaceSignature),
+                             intent: Intent, flags: u32) -> LCMSResult<Self> {
         if handle.is_null() {
             Err(Error::ObjectCreationError)
         } else {
             Ok(Transform {
                 handle,
-                _from: Self::check_format::<InputPixelFormat>(in_format, true),
-                _to: Self::check_format::<OutputPixelFormat>(out_format, false),
+                input_color_space,
+                output_color_space,
+                intent,
+                flags,
+                _from: PhantomData,
+                _to: PhantomData,
                 _context_ref: PhantomData,
                 _flags_ref: PhantomData,
             })
         }
     }
 
-    #[inline]
-    fn check_format<Z>(format: PixelFormat, input: bool) -> PhantomData<Z> {
-        assert!(!format.planar(), "Planar not supported");
-        assert_eq!(format.bytes_per_pixel(),
-                   std::mem::size_of::<Z>(),
-                   "PixelFormat {:?} has {} bytes per pixel, but the {} format has {}",
-                   format,
-                   format.bytes_per_pixel(),
-                   if input {"input"} else {"output"},
-                   std::mem::size_of::<Z>());
-        PhantomData
+    /// Checks pixel formats against the pixel types and color spaces at both ends of the chain of profiles.
+    /// Null transforms only copy the pixels, so their formats don't need to match the profiles.
+    pub(crate) fn check_formats(in_format: PixelFormat, out_format: PixelFormat, (entry, exit): (ColorSpaceSignature, ColorSpaceSignature), flags: u32) -> LCMSResult<()> {
+        if 0 != flags & ffi::FLAGS_NULLTRANSFORM {
+            check_format::<InputPixelFormat>(in_format, None)?;
+            return check_format::<OutputPixelFormat>(out_format, None);
+        }
+        check_format::<InputPixelFormat>(in_format, Some(entry))?;
+        check_format::<OutputPixelFormat>(out_format, Some(exit))
     }
 
     /// This function translates bitmaps according of parameters setup when creating the color transform.
+    #[track_caller]
     pub fn transform_pixels(&self, src: &[InputPixelFormat], dst: &mut [OutputPixelFormat]) {
+        assert!(!self.input_format().planar() && !self.output_format().planar(), "Use transform_planes() for planar formats");
         let size = src.len();
         assert_eq!(size, dst.len());
         assert!(size < std::u32::MAX as usize);
@@ -174,18 +315,146 @@ impl<InputPixelFormat: Copy + Clone, OutputPixelFormat: Copy + Clone, Ctx: Conte
         }
     }
 
+    /// Translates bitmaps in which each channel is stored in a separate plane, e.g. `RGB_8_PLANAR`.
+    ///
+    /// The planes must follow each other in the slice: first all samples of the first channel, then all samples of the second channel, and so on.
+    /// Extra (alpha) channels count as planes too. The length of a planar buffer must be a multiple of the number of its planes.
+    ///
+    /// Planar and non-planar (interleaved) formats can be mixed. An interleaved buffer has one element per pixel as usual.
+    /// Both buffers must contain the same number of pixels.
+    ///
+    /// Returns an error if the buffers are too large for LCMS or a planar format has no channels.
+    /// Panics if the lengths of the buffers don't match.
+    #[track_caller]
+    pub fn transform_planes(&self, src: &[InputPixelFormat], dst: &mut [OutputPixelFormat]) -> LCMSResult<()> {
+        let in_planes = Self::planes(self.input_format());
+        let out_planes = Self::planes(self.output_format());
+        if in_planes == 0 || out_planes == 0 {
+            return Err(Error::InvalidChannels);
+        }
+        assert_eq!(0, src.len() % in_planes, "Input length must be a multiple of {in_planes} planes");
+        let pixels = src.len() / in_planes;
+        assert_eq!(pixels * out_planes, dst.len(), "Output must have {out_planes} planes of {pixels} pixels");
+
+        let bytes_in = pixels * std::mem::size_of::<InputPixelFormat>();
+        let bytes_out = pixels * std::mem::size_of::<OutputPixelFormat>();
+        unsafe {
+            self.transform_line_stride(src.as_ptr().cast(), dst.as_mut_ptr().cast(),
+                pixels, 1,
+                bytes_in, bytes_out,
+                bytes_in, bytes_out)
+        }
+    }
+
+    /// Translates a 2D image, which may have padding at the end of each line, or be a sub-rectangle of a larger image.
+    ///
+    /// Strides are the distances in **bytes** between starts of consecutive lines. They must be at least `width` pixels long,
+    /// and a multiple of the alignment of the pixel type. `width` and `height` are in pixels.
+    ///
+    /// For planar formats, each plane consists of `height` lines of `stride` bytes, and planes follow each other in the slice.
+    /// Use `transform_image_planes()` if there's padding between the planes.
+    ///
+    /// Returns an error if the image is too large for LCMS or a planar format has no channels.
+    /// Panics if the image doesn't fit in the slices.
+    #[track_caller]
+    pub fn transform_image(&self, src: &[InputPixelFormat], src_stride: usize, dst: &mut [OutputPixelFormat], dst_stride: usize, width: usize, height: usize) -> LCMSResult<()> {
+        let plane_stride = |format: PixelFormat, stride: usize| {
+            if format.planar() { stride.checked_mul(height).ok_or(Error::SizeOverflow) } else { Ok(0) }
+        };
+        let src_plane_stride = plane_stride(self.input_format(), src_stride)?;
+        let dst_plane_stride = plane_stride(self.output_format(), dst_stride)?;
+        self.transform_image_planes(src, src_stride, src_plane_stride, dst, dst_stride, dst_plane_stride, width, height)
+    }
+
+    /// Same as `transform_image()`, but planes of planar formats start every `plane_stride` bytes.
+    ///
+    /// Plane strides must be large enough for `height` lines, and a multiple of the alignment of the pixel type.
+    /// They're ignored for interleaved formats.
+    ///
+    /// Returns an error if the image is too large for LCMS or a planar format has no channels.
+    /// Panics if the image doesn't fit in the slices.
+    #[allow(clippy::too_many_arguments)]
+    #[track_caller]
+    pub fn transform_image_planes(&self, src: &[InputPixelFormat], src_stride: usize, src_plane_stride: usize,
+                                  dst: &mut [OutputPixelFormat], dst_stride: usize, dst_plane_stride: usize,
+                                  width: usize, height: usize) -> LCMSResult<()> {
+        let in_planes = Self::planes(self.input_format());
+        let out_planes = Self::planes(self.output_format());
+        if in_planes == 0 || out_planes == 0 {
+            return Err(Error::InvalidChannels);
+        }
+        Self::check_image_fits::<InputPixelFormat>(src.len(), src_stride, src_plane_stride, in_planes, width, height)?;
+        Self::check_image_fits::<OutputPixelFormat>(dst.len(), dst_stride, dst_plane_stride, out_planes, width, height)?;
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+        unsafe {
+            self.transform_line_stride(src.as_ptr().cast(), dst.as_mut_ptr().cast(),
+                width, height,
+                src_stride, dst_stride,
+                src_plane_stride, dst_plane_stride)
+        }
+    }
+
+    #[track_caller]
+    fn check_image_fits<Z>(len: usize, stride: usize, plane_stride: usize, planes: usize, width: usize, height: usize) -> LCMSResult<()> {
+        let line_bytes = width.checked_mul(std::mem::size_of::<Z>()).ok_or(Error::SizeOverflow)?;
+        assert!(stride >= line_bytes, "Stride {stride} is smaller than a line of {width} pixels ({line_bytes} bytes)");
+        assert_eq!(0, stride % std::mem::align_of::<Z>(), "Stride must be a multiple of the pixel type's alignment");
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+        let plane_bytes = (height - 1).checked_mul(stride).and_then(|b| b.checked_add(line_bytes)).ok_or(Error::SizeOverflow)?;
+        if planes > 1 {
+            assert!(plane_stride >= plane_bytes, "Plane stride {plane_stride} is smaller than a plane of {height} lines ({plane_bytes} bytes)");
+            assert_eq!(0, plane_stride % std::mem::align_of::<Z>(), "Plane stride must be a multiple of the pixel type's alignment");
+        }
+        let needed = (planes - 1).checked_mul(plane_stride).and_then(|b| b.checked_add(plane_bytes)).ok_or(Error::SizeOverflow)?;
+        let available = len * std::mem::size_of::<Z>();
+        assert!(needed <= available, "Image needs {needed} bytes, but the buffer has only {available}");
+        Ok(())
+    }
+
+    /// Number of elements of the buffer used for each pixel. Planar formats without channels have no planes.
+    #[inline]
+    fn planes(format: PixelFormat) -> usize {
+        if format.planar() {
+            if format.channels() == 0 {
+                return 0;
+            }
+            format.channels() + format.extra()
+        } else {
+            1
+        }
+    }
+
+    /// Caller must ensure that the strides fit in the buffers. LCMS takes all sizes as `u32`.
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn transform_line_stride(&self, src: *const c_void, dst: *mut c_void,
+                                    pixels_per_line: usize, line_count: usize,
+                                    bytes_per_line_in: usize, bytes_per_line_out: usize,
+                                    bytes_per_plane_in: usize, bytes_per_plane_out: usize) -> LCMSResult<()> {
+        let u32 = |size: usize| u32::try_from(size).map_err(|_| Error::SizeOverflow);
+        ffi::cmsDoTransformLineStride(self.handle, src, dst,
+            u32(pixels_per_line)?, u32(line_count)?,
+            u32(bytes_per_line_in)?, u32(bytes_per_line_out)?,
+            u32(bytes_per_plane_in)?, u32(bytes_per_plane_out)?);
+        Ok(())
+    }
+
     #[inline]
     pub fn new_flags_context(context: impl AsRef<Ctx>, input: &Profile<Ctx>, in_format: PixelFormat,
                              output: &Profile<Ctx>, out_format: PixelFormat,
                              intent: Intent, flags: Flags<Fl>)
                              -> LCMSResult<Self> {
+        let spaces = color_spaces(&[input, output]);
+        Self::check_formats(in_format, out_format, spaces, flags.bits())?;
         Self::new_handle(unsafe {
                              ffi::cmsCreateTransformTHR(context.as_ref().as_ptr(),
                                 input.handle, in_format,
                                 output.handle, out_format,
                                 intent, flags.bits())
-                         },
-                         in_format, out_format)
+                         }, spaces, intent, flags.bits())
     }
 
     #[inline]
@@ -194,26 +463,106 @@ impl<InputPixelFormat: Copy + Clone, OutputPixelFormat: Copy + Clone, Ctx: Conte
                         proofing: &Profile<Ctx>, intent: Intent, proofng_intent: Intent,
                         flags: Flags<Fl>)
                         -> LCMSResult<Self> {
+        let spaces = color_spaces(&[input, output]);
+        Self::check_formats(in_format, out_format, spaces, flags.bits())?;
         Self::new_handle(unsafe {
                              ffi::cmsCreateProofingTransformTHR(context.as_ref().as_ptr(), input.handle, in_format,
                                 output.handle, out_format,
                                 proofing.handle, intent, proofng_intent, flags.bits())
-                         },
-                         in_format, out_format)
+                         }, spaces, intent, flags.bits())
     }
 
     #[inline]
     pub fn new_multiprofile_context(context: impl AsRef<Ctx>, profiles: &[&Profile<Ctx>],
                                 in_format: PixelFormat, out_format: PixelFormat, intent: Intent, flags: Flags<Fl>) -> LCMSResult<Self> {
+        if profiles.is_empty() {
+            return Err(Error::MissingData);
+        }
+        let spaces = color_spaces(profiles);
+        Self::check_formats(in_format, out_format, spaces, flags.bits())?;
         let mut handles: Vec<_> = profiles.iter().map(|p| p.handle).collect();
         unsafe {
             Self::new_handle(
                 ffi::cmsCreateMultiprofileTransformTHR(context.as_ref().as_ptr(), handles.as_mut_ptr(), handles.len() as u32, in_format, out_format, intent, flags.bits()),
-                in_format,
-                out_format,
+                spaces, intent, flags.bits(),
             )
         }
     }
+
+    /// Changes pixel formats of the transform, reusing its precalculated color conversion.
+    /// This is much cheaper than creating a new transform, e.g. to handle both 8-bit and 16-bit images.
+    ///
+    /// The new formats must be for the same color spaces. It works only for transforms between integer (8 or 16-bit) formats,
+    /// and the transform must have been created with a 16-bit input format (8-bit transforms are optimized for 8-bit precision only).
+    /// If it fails, the unchanged transform is returned along with the error.
+    pub fn change_format<NewInputPixelFormat: Copy + Clone, NewOutputPixelFormat: Copy + Clone>(self, in_format: PixelFormat, out_format: PixelFormat)
+        -> Result<Transform<NewInputPixelFormat, NewOutputPixelFormat, Ctx, Fl>, (Self, Error)> {
+        let spaces = (self.input_color_space, self.output_color_space);
+        if let Err(err) = Transform::<NewInputPixelFormat, NewOutputPixelFormat, Ctx, Fl>::check_formats(in_format, out_format, spaces, self.flags) {
+            return Err((self, err));
+        }
+        if 0 == unsafe { ffi::cmsChangeBuffersFormat(self.handle, in_format, out_format) } {
+            return Err((self, Error::ObjectCreationError));
+        }
+        let this = ManuallyDrop::new(self);
+        let (handle, intent, flags) = (this.handle, this.intent, this.flags);
+        Transform::new_handle(handle, spaces, intent, flags).map_err(|err| (ManuallyDrop::into_inner(this), err))
+    }
+}
+
+/// Parallel versions of the transform functions. Requires the `rayon` Cargo feature.
+///
+/// The transform must be created with `Flags::NO_CACHE` and a `ThreadContext` (or the global context),
+/// so that it can be used from multiple threads at once.
+#[cfg(feature = "rayon")]
+impl<InputPixelFormat: Copy + Clone + Sync, OutputPixelFormat: Copy + Clone + Send, Ctx: Context + Send> Transform<InputPixelFormat, OutputPixelFormat, Ctx, DisallowCache> {
+    /// Same as `transform_pixels()`, but splits the work across rayon's thread pool.
+    #[inline]
+    #[track_caller]
+    pub fn par_transform_pixels(&self, src: &[InputPixelFormat], dst: &mut [OutputPixelFormat]) {
+        self.par_transform_pixels_min_chunk(src, dst, PAR_MIN_CHUNK);
+    }
+
+    /// Same as `par_transform_pixels()`, but no thread will get fewer than `min_chunk` pixels to process
+    /// (except the last chunk of the image).
+    #[track_caller]
+    pub fn par_transform_pixels_min_chunk(&self, src: &[InputPixelFormat], dst: &mut [OutputPixelFormat], min_chunk: usize) {
+        assert_eq!(src.len(), dst.len());
+        let chunk = par_chunk_size(src.len(), min_chunk);
+        src.par_chunks(chunk)
+            .zip(dst.par_chunks_mut(chunk))
+            .for_each(|(src, dst)| self.transform_pixels(src, dst));
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<PixelFormat: Copy + Clone + Send, Ctx: Context + Send> Transform<PixelFormat, PixelFormat, Ctx, DisallowCache> {
+    /// Same as `transform_in_place()`, but splits the work across rayon's thread pool.
+    #[inline]
+    #[track_caller]
+    pub fn par_transform_in_place(&self, srcdst: &mut [PixelFormat]) {
+        self.par_transform_in_place_min_chunk(srcdst, PAR_MIN_CHUNK);
+    }
+
+    /// Same as `par_transform_in_place()`, but no thread will get fewer than `min_chunk` pixels to process
+    /// (except the last chunk of the image).
+    #[track_caller]
+    pub fn par_transform_in_place_min_chunk(&self, srcdst: &mut [PixelFormat], min_chunk: usize) {
+        let chunk = par_chunk_size(srcdst.len(), min_chunk);
+        srcdst.par_chunks_mut(chunk)
+            .for_each(|chunk| self.transform_in_place(chunk));
+    }
+}
+
+/// Default minimum number of pixels processed by one thread
+#[cfg(feature = "rayon")]
+const PAR_MIN_CHUNK: usize = 4096;
+
+/// A few chunks per thread, to balance the load
+#[cfg(feature = "rayon")]
+fn par_chunk_size(len: usize, min_chunk: usize) -> usize {
+    let per_thread = len / (rayon::current_num_threads() * 4) + 1;
+    per_thread.max(min_chunk).max(1)
 }
 
 impl<F, T, C, L> Transform<F, T, C, L> {
@@ -228,6 +577,58 @@ impl<F, T, C, L> Transform<F, T, C, L> {
     pub fn output_format(&self) -> PixelFormat {
         unsafe { ffi::cmsGetTransformOutputFormat(self.handle) as PixelFormat }
     }
+
+    /// Color space of the input side of the transform, i.e. of the first profile
+    #[inline]
+    #[must_use]
+    pub fn input_color_space(&self) -> ColorSpaceSignature {
+        self.input_color_space
+    }
+
+    /// Color space of the output side of the transform, i.e. of the last profile
+    #[inline]
+    #[must_use]
+    pub fn output_color_space(&self) -> ColorSpaceSignature {
+        self.output_color_space
+    }
+
+    /// Rendering intent the transform has been created with.
+    /// For transforms from `TransformBuilder` it's the intent of the first profile.
+    #[inline]
+    #[must_use]
+    pub fn intent(&self) -> Intent {
+        self.intent
+    }
+
+    /// Flags the transform has been created with.
+    ///
+    /// The flags are returned without compile-time cache information, but `Flags::NO_CACHE` can still be checked with `has()`.
+    #[inline]
+    #[must_use]
+    pub fn flags(&self) -> Flags {
+        Flags::from_bits(self.flags)
+    }
+
+    /// Palette used by a transform created from a named color profile
+    #[must_use]
+    pub fn named_color_list(&self) -> Option<&NamedColorListRef> {
+        unsafe {
+            let list = ffi::cmsGetNamedColorList(self.handle);
+            if list.is_null() {
+                None
+            } else {
+                Some(NamedColorListRef::from_ptr(list))
+            }
+        }
+    }
+
+    /// Raw pointer to the LCMS context of this transform (null for the global context).
+    /// It's unique for each `ThreadContext`, so it can be used to tell contexts apart.
+    #[inline]
+    #[must_use]
+    pub fn context_id(&self) -> ffi::Context {
+        unsafe { ffi::cmsGetTransformContextID(self.handle) }
+    }
 }
 
 impl<F, T, L> Transform<F, T, GlobalContext, L> {
@@ -294,6 +695,9 @@ impl<F, T, C, L> fmt::Debug for Transform<F, T, C, L> {
         ));
         s.field("input_format", &self.input_format());
         s.field("output_format", &self.output_format());
+        s.field("input_color_space", &self.input_color_space);
+        s.field("output_color_space", &self.output_color_space);
+        s.field("intent", &self.intent);
         s.finish()
     }
 }
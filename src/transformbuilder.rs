@@ -0,0 +1,118 @@
+use crate::context::Context;
+use crate::transform::color_spaces;
+use crate::*;
+use std::fmt;
+use std::ptr;
+
+/// A profile in the chain of `TransformBuilder`
+struct Step<'a, Ctx> {
+    profile: &'a Profile<Ctx>,
+    intent: Intent,
+    black_point_compensation: bool,
+    adaptation_state: f64,
+}
+
+/// Creates a `Transform` from a chain of profiles, each with its own rendering intent, black point compensation and adaptation state.
+///
+/// This is the most flexible way of creating a transform (it's `cmsCreateExtendedTransform`), useful in print workflows.
+///
+/// ```rust,ignore
+/// let t: Transform<[u8; 3], [u8; 4]> = TransformBuilder::new()
+///     .step(&srgb, Intent::Perceptual, false, 1.)
+///     .step(&press, Intent::RelativeColorimetric, true, 1.)
+///     .build(PixelFormat::RGB_8, PixelFormat::CMYK_8, Flags::default())?;
+/// ```
+pub struct TransformBuilder<'a, Ctx = GlobalContext> {
+    steps: Vec<Step<'a, Ctx>>,
+    gamut_check: Option<(&'a Profile<Ctx>, usize)>,
+}
+
+impl<'a, Ctx: Context> TransformBuilder<'a, Ctx> {
+    /// Start with an empty chain of profiles. Add at least one profile with `step()`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            steps: Vec::new(),
+            gamut_check: None,
+        }
+    }
+
+    /// Appends a profile to the chain.
+    ///
+    ///  * `intent`: rendering intent used when linking this profile with the next one
+    ///  * `black_point_compensation`: whether to use black point compensation for this profile
+    ///  * `adaptation_state`: for absolute colorimetric intent. 0=Not adapted, 1=Complete adaptation, in-between=Partial adaptation.
+    #[must_use]
+    pub fn step(mut self, profile: &'a Profile<Ctx>, intent: Intent, black_point_compensation: bool, adaptation_state: f64) -> Self {
+        self.steps.push(Step {
+            profile,
+            intent,
+            black_point_compensation,
+            adaptation_state,
+        });
+        self
+    }
+
+    /// Marks colors that are out of gamut of the `profile`. Requires `Flags::GAMUT_CHECK`.
+    ///
+    /// `position` is index of the profile in the chain, whose PCS is used for checking the gamut.
+    #[must_use]
+    pub fn gamut_check(mut self, profile: &'a Profile<Ctx>, position: usize) -> Self {
+        self.gamut_check = Some((profile, position));
+        self
+    }
+
+    /// Creates the transform using the given context. See `Transform` for description of the formats and flags.
+    pub fn build_context<InputPixelFormat: Copy + Clone, OutputPixelFormat: Copy + Clone, Fl: CacheFlag>(&self, context: impl AsRef<Ctx>,
+        in_format: PixelFormat, out_format: PixelFormat, flags: Flags<Fl>) -> LCMSResult<Transform<InputPixelFormat, OutputPixelFormat, Ctx, Fl>> {
+        if self.steps.is_empty() {
+            return Err(Error::MissingData);
+        }
+        let profiles: Vec<_> = self.steps.iter().map(|s| s.profile).collect();
+        let spaces = color_spaces(&profiles);
+        Transform::<InputPixelFormat, OutputPixelFormat, Ctx, Fl>::check_formats(in_format, out_format, spaces, flags.bits())?;
+
+        let mut handles: Vec<_> = profiles.iter().map(|p| p.handle).collect();
+        let mut bpc: Vec<_> = self.steps.iter().map(|s| i32::from(s.black_point_compensation)).collect();
+        let mut intents: Vec<_> = self.steps.iter().map(|s| s.intent).collect();
+        let mut adaptation_states: Vec<_> = self.steps.iter().map(|s| s.adaptation_state).collect();
+        let (gamut_profile, gamut_position) = match self.gamut_check {
+            Some((profile, position)) => (profile.handle, position as u32),
+            None => (ptr::null_mut(), 0),
+        };
+        Transform::new_handle(unsafe {
+            ffi::cmsCreateExtendedTransform(context.as_ref().as_ptr(),
+                handles.len() as u32, handles.as_mut_ptr(),
+                bpc.as_mut_ptr().cast(), intents.as_mut_ptr().cast(), adaptation_states.as_mut_ptr(),
+                gamut_profile, gamut_position,
+                in_format, out_format, flags.bits())
+        }, spaces, self.steps[0].intent, flags.bits())
+    }
+}
+
+impl<'a> TransformBuilder<'a, GlobalContext> {
+    /// Creates the transform. See `Transform` for description of the formats and flags.
+    #[inline]
+    pub fn build<InputPixelFormat: Copy + Clone, OutputPixelFormat: Copy + Clone, Fl: CacheFlag>(&self,
+        in_format: PixelFormat, out_format: PixelFormat, flags: Flags<Fl>) -> LCMSResult<Transform<InputPixelFormat, OutputPixelFormat, GlobalContext, Fl>> {
+        self.build_context(GlobalContext::new(), in_format, out_format, flags)
+    }
+}
+
+impl<'a, Ctx: Context> Default for TransformBuilder<'a, Ctx> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, Ctx> fmt::Debug for TransformBuilder<'a, Ctx> {
+    #[cold]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut l = f.debug_list();
+        for s in &self.steps {
+            l.entry(&(s.intent, s.black_point_compensation, s.adaptation_state));
+        }
+        l.finish()
+    }
+}
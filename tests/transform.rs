@@ -33,6 +33,42 @@ fn gray() {
     tr.transform_in_place(&mut [0u32; 1]);
 }
 
+#[test]
+fn format_errors() {
+    let srgb = Profile::new_srgb();
+    let err = Transform::<u8, [u8; 3]>::new(&srgb, PixelFormat::RGB_8, &srgb, PixelFormat::RGB_8, Intent::Perceptual).unwrap_err();
+    assert_eq!(Error::PixelSizeMismatch { expected: 3, actual: 1 }, err);
+    let err = Transform::<[u8; 3], [u16; 4]>::new(&srgb, PixelFormat::RGB_8, &srgb, PixelFormat::CMYK_16, Intent::Perceptual).unwrap_err();
+    assert_eq!(Error::ColorSpaceMismatch(ColorSpaceSignature::RgbData), err);
+    let gray = Profile::new_icc(GRAY_PROFILE).unwrap();
+    let ok = Transform::<[u8; 3], u8>::new(&srgb, PixelFormat::RGB_8, &gray, PixelFormat::GRAY_8, Intent::Perceptual);
+    assert!(ok.is_ok());
+    let err = Transform::<u8, [u8; 3]>::new(&srgb, PixelFormat::GRAY_8, &gray, PixelFormat::RGB_8, Intent::Perceptual).unwrap_err();
+    assert_eq!(Error::ColorSpaceMismatch(ColorSpaceSignature::RgbData), err);
+}
+
+#[test]
+fn null_transform_formats() {
+    // null transforms only copy pixels, so they don't need formats of the profiles' color spaces
+    let srgb = Profile::new_srgb();
+    let tr = Transform::new_flags(&srgb, PixelFormat::GRAY_8, &srgb, PixelFormat::GRAY_8, Intent::Perceptual, Flags::NULL_TRANSFORM).unwrap();
+    let mut dst = [0u8; 3];
+    tr.transform_pixels(&[1u8, 2, 3], &mut dst);
+    assert_eq!([1, 2, 3], dst);
+    let err = Transform::<u8, u8>::new_flags(&srgb, PixelFormat::RGB_8, &srgb, PixelFormat::GRAY_8, Intent::Perceptual, Flags::NULL_TRANSFORM).unwrap_err();
+    assert_eq!(Error::PixelSizeMismatch { expected: 3, actual: 1 }, err);
+}
+
+#[test]
+fn lab_profile_with_xyz_pcs() {
+    // LCMS uses the first profile in the output direction when its color space is Lab or XYZ
+    let mut lab = Profile::new_lab4_context(GlobalContext::new(), &white_point_from_temp(5000.).unwrap()).unwrap();
+    lab.set_pcs(ColorSpaceSignature::XYZData);
+    let tr = Transform::<[f64; 3], [f64; 3]>::new(&lab, PixelFormat::XYZ_DBL, &Profile::new_xyz(), PixelFormat::XYZ_DBL, Intent::Perceptual).unwrap();
+    assert_eq!(ColorSpaceSignature::XYZData, tr.input_color_space());
+    assert_eq!(ColorSpaceSignature::XYZData, tr.output_color_space());
+}
+
 const GRAY_PROFILE: &[u8] = include_bytes!("gray18.icc");
 const SGRAY_PROFILE: &[u8] = include_bytes!("sGray.icc");
 
@@ -104,6 +140,211 @@ fn transform() {
     ], dst);
 }
 
+#[test]
+fn planar() {
+    let srgb = Profile::new_srgb();
+    let tr = Transform::new(&srgb, PixelFormat::RGB_8_PLANAR, &srgb, PixelFormat::RGB_8, Intent::Perceptual).unwrap();
+    let mut dst = [[0u8; 3]; 2];
+    tr.transform_planes(&[1u8, 2, 10, 20, 100, 200], &mut dst).unwrap();
+    assert_eq!([[1, 10, 100], [2, 20, 200]], dst);
+
+    let tr = Transform::new(&srgb, PixelFormat::RGB_8, &srgb, PixelFormat::RGB_8_PLANAR, Intent::Perceptual).unwrap();
+    let mut planes = [0u8; 6];
+    tr.transform_planes(&dst, &mut planes).unwrap();
+    assert_eq!([1, 2, 10, 20, 100, 200], planes);
+}
+
+#[test]
+#[should_panic]
+fn planar_bad_length() {
+    let srgb = Profile::new_srgb();
+    let tr = Transform::new(&srgb, PixelFormat::RGB_8_PLANAR, &srgb, PixelFormat::RGB_8_PLANAR, Intent::Perceptual).unwrap();
+    let _ = tr.transform_planes(&[0u8; 5], &mut [0u8; 6]);
+}
+
+#[test]
+#[should_panic]
+fn planar_in_place() {
+    let gray = Profile::new_icc(GRAY_PROFILE).unwrap();
+    let srgb = Profile::new_srgb();
+    let tr = Transform::<u8, u8>::new(&gray, PixelFormat::GRAY_8, &srgb, PixelFormat::RGB_8_PLANAR, Intent::Perceptual).unwrap();
+    tr.transform_in_place(&mut [0u8; 3]);
+}
+
+#[test]
+fn strided_image() {
+    let srgb = Profile::new_srgb();
+    let tr = Transform::new(&srgb, PixelFormat::RGB_8, &srgb, PixelFormat::RGBA_8, Intent::Perceptual).unwrap();
+    // 2x2 pixels, lines padded to 9 bytes; the last line does not need padding
+    let src = [
+        [1u8, 1, 1], [2, 2, 2], [0xEE, 0xEE, 0xEE],
+        [3, 3, 3], [4, 4, 4],
+    ];
+    let mut dst = [[0u8; 4]; 6];
+    tr.transform_image(&src, 9, &mut dst, 12, 2, 2).unwrap();
+    assert_eq!([
+        [1, 1, 1, 0], [2, 2, 2, 0], [0, 0, 0, 0],
+        [3, 3, 3, 0], [4, 4, 4, 0], [0, 0, 0, 0],
+    ], dst);
+}
+
+#[test]
+#[should_panic]
+fn strided_image_too_small() {
+    let srgb = Profile::new_srgb();
+    let tr = Transform::new(&srgb, PixelFormat::RGB_8, &srgb, PixelFormat::RGB_8, Intent::Perceptual).unwrap();
+    let _ = tr.transform_image(&[[0u8; 3]; 4], 9, &mut [[0u8; 3]; 6], 9, 2, 2);
+}
+
+#[test]
+fn strided_image_overflow() {
+    let srgb = Profile::new_srgb();
+    let tr = Transform::new(&srgb, PixelFormat::RGB_8, &srgb, PixelFormat::RGB_8, Intent::Perceptual).unwrap();
+    // interleaved formats have no planes, so there's no plane stride to overflow
+    tr.transform_image(&[[0u8; 3]; 0], usize::MAX / 2, &mut [], usize::MAX / 2, 0, 4).unwrap();
+    assert_eq!(Err(Error::SizeOverflow), tr.transform_image(&[[0u8; 3]; 1], usize::MAX / 2, &mut [[0u8; 3]; 1], 3, 1, 3));
+}
+
+#[test]
+fn strided_planes() {
+    let srgb = Profile::new_srgb();
+    let tr = Transform::new(&srgb, PixelFormat::RGB_8_PLANAR, &srgb, PixelFormat::RGB_8_PLANAR, Intent::Perceptual).unwrap();
+    // 2x1 pixels, lines padded to 3 bytes, planes padded to 4 bytes
+    let src = [
+        1u8, 2, 0xEE, 0xEE,
+        10, 20, 0xEE, 0xEE,
+        100, 200,
+    ];
+    let mut dst = [0u8; 6];
+    tr.transform_image_planes(&src, 3, 4, &mut dst, 2, 2, 2, 1).unwrap();
+    assert_eq!([1, 2, 10, 20, 100, 200], dst);
+}
+
+#[test]
+#[should_panic]
+fn strided_planes_overlap() {
+    let srgb = Profile::new_srgb();
+    let tr = Transform::new(&srgb, PixelFormat::RGB_8_PLANAR, &srgb, PixelFormat::RGB_8_PLANAR, Intent::Perceptual).unwrap();
+    let _ = tr.transform_image_planes(&[0u8; 12], 2, 3, &mut [0u8; 12], 2, 4, 2, 2);
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn parallel() {
+    let srgb = Profile::new_srgb();
+    let gray = Profile::new_icc(GRAY_PROFILE).unwrap();
+    let tr = Transform::new_flags_context(GlobalContext::new(), &gray, PixelFormat::GRAY_8, &srgb, PixelFormat::RGB_8, Intent::Perceptual, Flags::NO_CACHE).unwrap();
+    let src: Vec<u8> = (0..100_000).map(|i| i as u8).collect();
+    let mut expected = vec![[0u8; 3]; src.len()];
+    tr.transform_pixels(&src, &mut expected);
+    let mut dst = vec![[0u8; 3]; src.len()];
+    tr.par_transform_pixels_min_chunk(&src, &mut dst, 100);
+    assert_eq!(expected, dst);
+
+    let tr = Transform::new_flags_context(GlobalContext::new(), &srgb, PixelFormat::RGB_8, &srgb, PixelFormat::RGB_8, Intent::Perceptual, Flags::NO_CACHE).unwrap();
+    tr.par_transform_in_place(&mut dst);
+    assert_eq!(expected, dst);
+}
+
+#[test]
+fn typed() {
+    let gray = Profile::new_icc(GRAY_PROFILE).unwrap();
+    let srgb = Profile::new_srgb();
+    let tr = Transform::<Gray<u8>, Bgr<u8>>::new_typed(&gray, &srgb, Intent::Perceptual).unwrap();
+    let mut dst = [Bgr::default(); 2];
+    tr.transform_pixels(&[Gray { v: 0 }, Gray { v: 100 }], &mut dst);
+    assert_eq!([Bgr { b: 0, g: 0, r: 0 }, Bgr { b: 119, g: 119, r: 119 }], dst);
+
+    let err = Transform::<Rgb<u8>, Rgb<u8>>::new_typed(&gray, &srgb, Intent::Perceptual).unwrap_err();
+    assert_eq!(Error::ColorSpaceMismatch(ColorSpaceSignature::GrayData), err);
+    let err = Transform::<Rgb<u8>, Cmyk<u8>>::new_typed(&srgb, &srgb, Intent::Perceptual).unwrap_err();
+    assert_eq!(Error::ColorSpaceMismatch(ColorSpaceSignature::RgbData), err);
+}
+
+#[test]
+fn change_format() {
+    let gray = Profile::new_icc(GRAY_PROFILE).unwrap();
+    let srgb = Profile::new_srgb();
+    let tr = Transform::new(&gray, PixelFormat::GRAY_16, &srgb, PixelFormat::BGRA_16, Intent::Perceptual).unwrap();
+    let mut dest = [[0u16; 4]; 1];
+    tr.transform_pixels(&[0xFFFFu16], &mut dest);
+    assert_eq!([[0xFFFF, 0xFFFF, 0xFFFF, 0]], dest);
+
+    let tr = tr.change_format(PixelFormat::GRAY_8, PixelFormat::RGB_8).unwrap();
+    let mut dest = [[0u8; 3]; 1];
+    tr.transform_pixels(&[100u8], &mut dest);
+    assert_eq!([[119, 119, 119]], dest);
+
+    let (tr, err) = tr.change_format::<u8, [u8; 4]>(PixelFormat::GRAY_8, PixelFormat::CMYK_8).unwrap_err();
+    assert_eq!(Error::ColorSpaceMismatch(ColorSpaceSignature::RgbData), err);
+    tr.transform_pixels(&[100u8], &mut dest);
+    assert_eq!([[119, 119, 119]], dest);
+
+    let tr_8bit = Transform::<u8, [u8; 3]>::new(&gray, PixelFormat::GRAY_8, &srgb, PixelFormat::RGB_8, Intent::Perceptual).unwrap();
+    let (tr_8bit, err) = tr_8bit.change_format::<u16, [u16; 3]>(PixelFormat::GRAY_16, PixelFormat::RGB_16).unwrap_err();
+    assert_eq!(Error::ObjectCreationError, err);
+    tr_8bit.transform_pixels(&[100u8], &mut dest);
+    assert_eq!([[119, 119, 119]], dest);
+}
+
+#[test]
+fn builder() {
+    let gray = Profile::new_icc(GRAY_PROFILE).unwrap();
+    let srgb = Profile::new_srgb();
+    let tr: Transform<u8, [u8; 3]> = TransformBuilder::new()
+        .step(&gray, Intent::Perceptual, false, 1.)
+        .step(&srgb, Intent::Perceptual, true, 1.)
+        .build(PixelFormat::GRAY_8, PixelFormat::RGB_8, Flags::default())
+        .unwrap();
+    let mut dest = [[0u8; 3]; 3];
+    tr.transform_pixels(&[0u8, 100, 255], &mut dest);
+    assert_eq!([[0, 0, 0], [119, 119, 119], [255, 255, 255]], dest);
+
+    let empty = TransformBuilder::<GlobalContext>::new().build::<u8, u8, _>(PixelFormat::GRAY_8, PixelFormat::GRAY_8, Flags::default());
+    assert_eq!(Error::MissingData, empty.unwrap_err());
+}
+
+#[test]
+fn introspection() {
+    let gray = Profile::new_icc(GRAY_PROFILE).unwrap();
+    let srgb = Profile::new_srgb();
+    let tr = Transform::<u8, [u8; 3]>::new_flags(&gray, PixelFormat::GRAY_8, &srgb, PixelFormat::RGB_8, Intent::Saturation, Flags::BLACKPOINT_COMPENSATION).unwrap();
+    assert_eq!(ColorSpaceSignature::GrayData, tr.input_color_space());
+    assert_eq!(ColorSpaceSignature::RgbData, tr.output_color_space());
+    assert_eq!(Intent::Saturation, tr.intent());
+    assert!(tr.flags().has(Flags::BLACKPOINT_COMPENSATION));
+    assert!(!tr.flags().has(Flags::NO_CACHE));
+    assert!(tr.named_color_list().is_none());
+    assert!(tr.context_id().is_null());
+
+    let c = ThreadContext::new();
+    let p = Profile::new_srgb_context(&c);
+    let tr = Transform::<[u8; 3], [u8; 3], _, _>::new_flags_context(&c, &p, PixelFormat::RGB_8, &p, PixelFormat::RGB_8, Intent::Perceptual, Flags::NO_CACHE).unwrap();
+    assert!(tr.flags().has(Flags::NO_CACHE));
+    assert!(!tr.context_id().is_null());
+}
+
+#[test]
+fn named_color() {
+    let mut list = NamedColorList::new(2, 4, "PANTONE ", " C").unwrap();
+    list.append("Red", [0x8000, 0xC000, 0xA000], [0, 0xFFFF, 0xFFFF, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]).unwrap();
+    list.append("Black", [0, 0x8080, 0x8080], [0, 0, 0, 0xFFFF, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]).unwrap();
+
+    let mut named = Profile::new_placeholder();
+    named.set_device_class(ProfileClassSignature::NamedColorClass);
+    named.set_color_space(ColorSpaceSignature::CmykData);
+    named.set_pcs(ColorSpaceSignature::LabData);
+    named.write_tag(TagSignature::NamedColor2Tag, Tag::NamedColorList(&list)).unwrap();
+
+    let tr = Transform::<u16, [u16; 4]>::new_named_color(&named, PixelFormat::CMYK_16, Intent::Perceptual).unwrap();
+    assert_eq!(2, tr.named_color_list().unwrap().len());
+    let black = tr.named_color_index("Black").unwrap();
+    assert_eq!(None, tr.named_color_index("Blue"));
+    let mut dst = [[1u16; 4]; 2];
+    tr.transform_pixels(&[black, 0], &mut dst);
+    assert_eq!([[0, 0, 0, 0xFFFF], [0, 0xFFFF, 0xFFFF, 0]], dst);
+}
+
 #[test]
 fn context() {
     let c = ThreadContext::new();